@@ -3,6 +3,11 @@ use std::rc::Rc;
 use napi_sys::*;
 use std::cell::Cell;
 use std::path::PathBuf;
+use std::collections::VecDeque;
+use std::ffi::OsString;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
+use std::ops::Deref;
 
 use crate::JsValue;
 use crate::prelude::*;
@@ -42,11 +47,232 @@ impl Arguments {
         self.current_arg.get()
     }
 
+    /// Consumes all the remaining arguments
+    pub(crate) fn consume_all(&self) {
+        self.current_arg.set(self.args.len());
+    }
+
+    /// Returns an error if some arguments have not been consumed.
+    ///
+    /// This is used by functions in strict mode.
+    pub(crate) fn check_surplus(&self) -> JsResult<()> {
+        let consumed = self.current_arg.get();
+        match self.args.len() {
+            len if len > consumed => Err(ArgumentsError::Surplus(consumed, len).into()),
+            _ => Ok(())
+        }
+    }
+
     pub fn next_arg<'e>(&self) -> Option<JsAny<'e>> {
         let current = self.current_arg.get();
         self.current_arg.set(current + 1);
         self.args.get(current).and_then(|v| JsAny::from(*v).ok())
     }
+
+    /// Returns the next argument without consuming it
+    pub(crate) fn peek_arg<'e>(&self) -> Option<JsAny<'e>> {
+        self.get(self.current_arg.get())
+    }
+
+    /// Consumes the next argument
+    pub(crate) fn skip_arg(&self) {
+        self.current_arg.set(self.current_arg.get() + 1);
+    }
+
+    /// Returns the number of arguments the function has been called with
+    pub(crate) fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns the argument at `index` without consuming it
+    pub(crate) fn get<'e>(&self, index: usize) -> Option<JsAny<'e>> {
+        self.args.get(index).and_then(|v| JsAny::from(*v).ok())
+    }
+
+    /// Restarts the arguments extraction from the first argument.
+    ///
+    /// Used to try another overload of a function with the same arguments.
+    pub(crate) fn reset(&self) {
+        self.current_arg.set(0);
+    }
+}
+
+/// Kind of a javascript argument in a [`Signature`]
+///
+/// [`Signature`]: ./struct.Signature.html
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ArgKind {
+    /// The argument has to be present
+    Required,
+    /// The argument can be omitted
+    Optional,
+    /// The argument receives all the remaining arguments
+    Rest,
+}
+
+/// Description of one javascript argument expected by a function
+#[derive(Copy, Clone)]
+pub struct ArgSignature {
+    name: &'static str,
+    kind: ArgKind,
+    check: fn(&JsAny) -> bool,
+}
+
+impl ArgSignature {
+    /// Creates an argument accepting values for which `check` returns true.
+    ///
+    /// `name` is used in error messages.
+    pub fn new(name: &'static str, check: fn(&JsAny) -> bool) -> ArgSignature {
+        ArgSignature { name, kind: ArgKind::Required, check }
+    }
+
+    /// Creates an argument accepting any javascript value
+    pub fn any() -> ArgSignature {
+        ArgSignature::new("any", |_| true)
+    }
+
+    /// Returns the argument name, used in error messages
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the argument kind
+    pub fn kind(&self) -> ArgKind {
+        self.kind
+    }
+
+    /// Checks if the javascript value can be received by this argument
+    pub fn accepts(&self, value: &JsAny) -> bool {
+        (self.check)(value)
+    }
+}
+
+impl std::fmt::Debug for ArgSignature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ArgSignature")
+         .field("name", &self.name)
+         .field("kind", &self.kind)
+         .finish()
+    }
+}
+
+/// List of the javascript arguments expected by a function.
+///
+/// It is built from the [`FromArguments`] types of the function parameters
+/// and used to select an overload without converting the arguments.
+///
+/// [`FromArguments`]: ./trait.FromArguments.html
+#[derive(Clone, Debug, Default)]
+pub struct Signature {
+    args: Vec<ArgSignature>,
+}
+
+impl Signature {
+    /// Creates the signature of the type `A`
+    pub fn of<A: FromArguments>() -> Signature {
+        let mut signature = Signature::default();
+        A::signature(&mut signature);
+        signature
+    }
+
+    /// Adds an argument to the signature
+    pub fn push(&mut self, arg: ArgSignature) {
+        self.args.push(arg);
+    }
+
+    /// Returns the arguments of the signature
+    pub fn args(&self) -> &[ArgSignature] {
+        &self.args
+    }
+
+    /// Makes the arguments added by `fun` optional
+    pub fn optional(&mut self, fun: impl FnOnce(&mut Signature)) {
+        let start = self.args.len();
+        fun(self);
+        for arg in &mut self.args[start..] {
+            arg.kind = ArgKind::Optional;
+        }
+    }
+
+    /// Makes the argument added by `fun` receive all the remaining arguments
+    pub fn rest(&mut self, fun: impl FnOnce(&mut Signature)) {
+        let start = self.args.len();
+        fun(self);
+        for arg in &mut self.args[start..] {
+            arg.kind = ArgKind::Rest;
+        }
+    }
+
+    /// Returns the maximum number of javascript arguments consumed.  
+    /// `None` when there is no limit.
+    pub(crate) fn max_args(&self) -> Option<usize> {
+        match self.args.iter().any(|arg| arg.kind == ArgKind::Rest) {
+            true => None,
+            _ => Some(self.args.len())
+        }
+    }
+
+    /// Checks the javascript arguments against the signature.
+    ///
+    /// The values are only inspected, nothing is converted.  
+    /// In strict mode, surplus arguments are rejected.  
+    /// Returns the reason of the rejection.
+    pub(crate) fn check(&self, args: &Arguments, strict: bool) -> std::result::Result<(), String> {
+        match self.max_args() {
+            Some(max) if strict && args.len() > max => {
+                return Err(format!(
+                    "Too many arguments, expected at most {} but got {}", max, args.len()
+                ));
+            }
+            _ => {}
+        }
+        for (index, arg) in self.args.iter().enumerate() {
+            if arg.kind == ArgKind::Rest {
+                return self.check_rest(arg, index, args);
+            }
+            match args.get(index) {
+                // `undefined` and `null` are similar to a missing optional argument
+                Some(JsAny::Undefined(_)) | Some(JsAny::Null(_)) if arg.kind == ArgKind::Optional => {}
+                Some(ref value) if !arg.accepts(value) => {
+                    return Err(format!(
+                        "arg {}: expected {}, got {}", index + 1, arg.name, value.type_name()
+                    ));
+                }
+                Some(_) => {}
+                None if arg.kind == ArgKind::Required => {
+                    return Err(format!("arg {}: missing {}", index + 1, arg.name));
+                }
+                None => {}
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks the remaining arguments, starting at `start`, against a rest argument
+    fn check_rest(&self, arg: &ArgSignature, start: usize, args: &Arguments) -> std::result::Result<(), String> {
+        for index in start..args.len() {
+            match args.get(index) {
+                Some(ref value) if !arg.accepts(value) => {
+                    return Err(format!(
+                        "arg {}: expected {}, got {}", index + 1, arg.name, value.type_name()
+                    ));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let args: Vec<_> = self.args.iter().map(|arg| match arg.kind {
+            ArgKind::Required => arg.name.to_owned(),
+            ArgKind::Optional => format!("{}?", arg.name),
+            ArgKind::Rest => format!("...{}", arg.name),
+        }).collect();
+        write!(f, "({})", args.join(", "))
+    }
 }
 
 /// Trait to implement for types received by arguments on js function calls.
@@ -73,6 +299,15 @@ impl Arguments {
 /// [`Pinar`]: ./derive.Pinar.html
 pub trait FromArguments: Sized {
     fn from_args(args: &Arguments) -> JsResult<Self>;
+
+    /// Describes the javascript arguments consumed by `from_args`.
+    ///
+    /// It is used to select an overload of a function before converting
+    /// its arguments.  
+    /// The default implementation consumes 1 argument of any type.
+    fn signature(signature: &mut Signature) {
+        signature.push(ArgSignature::any());
+    }
 }
 
 macro_rules! from_args_tuples {
@@ -87,9 +322,16 @@ macro_rules! from_args_tuples {
                 #[allow(non_snake_case, unused_variables)]
                 fn from_args(args: &Arguments) -> JsResult<Self> {
                     // FromArguments::from_args needs to be called in order
-                    $(let $tuple = $tuple::from_args(args)?;)*
+                    $(let $tuple = $tuple::from_args(args).map_err(|e| {
+                        ArgumentsError::locate(e, args.arg_number())
+                    })?;)*
                     Ok(($($tuple,)*))
                 }
+
+                #[allow(unused_variables)]
+                fn signature(signature: &mut Signature) {
+                    $($tuple::signature(signature);)*
+                }
             }
         )*
     }
@@ -118,17 +360,143 @@ impl<A> FromArguments for Option<A>
 where
     A: FromArguments
 {
+    /// A missing argument, `undefined` or `null` are converted to `None`
     fn from_args(args: &Arguments) -> JsResult<Self> {
-        match A::from_args(args) {
-            Ok(arg) => Ok(Some(arg)),
-            Err(e) => {
-                if let Some(ArgumentsError::Missing(_)) = e.downcast_ref::<ArgumentsError>() {
-                    return Ok(None);
-                }
-                Err(e)
+        match args.peek_arg() {
+            None | Some(JsAny::Undefined(_)) | Some(JsAny::Null(_)) => {
+                args.skip_arg();
+                Ok(None)
             }
+            _ => A::from_args(args).map(Some)
         }
     }
+
+    fn signature(signature: &mut Signature) {
+        signature.optional(A::signature);
+    }
+}
+
+/// Argument replaced by `T::default()` when it is missing, `undefined` or `null`.
+///
+/// This is similar to default parameters in javascript.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(retries: OrDefault<i64>) -> i64 {
+///     *retries
+/// }
+/// // From javascript:
+/// // my_func() // 0
+/// // my_func(undefined) // 0
+/// // my_func(null) // 0
+/// // my_func(3) // 3
+/// ```
+pub struct OrDefault<T>(pub T);
+
+impl<T> OrDefault<T> {
+    /// Returns the inner value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> Deref for OrDefault<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> FromArguments for OrDefault<T>
+where
+    T: FromArguments + Default
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        match args.peek_arg() {
+            None | Some(JsAny::Undefined(_)) | Some(JsAny::Null(_)) => {
+                args.skip_arg();
+                Ok(OrDefault(T::default()))
+            }
+            _ => T::from_args(args).map(OrDefault)
+        }
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.optional(T::signature);
+    }
+}
+
+/// Options object received as argument, usually the last one.
+///
+/// When the argument is missing, `undefined` or `null`, `T::default()`
+/// is used.  
+/// The object is converted with `serde`: default values for fields,
+/// rejection of unknown keys and renaming of keys are configured with
+/// serde attributes.
+///
+/// # Example
+///
+/// ```
+/// #[derive(Deserialize, Default)]
+/// #[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+/// struct ConnectOptions {
+///     max_retries: u32,
+///     timeout_ms: u64,
+/// }
+///
+/// #[pinar]
+/// fn connect(url: String, options: Options<ConnectOptions>) -> JsResult<()> {
+///     let retries = options.max_retries;
+///     Ok(())
+/// }
+/// // From javascript:
+/// // connect("localhost");
+/// // connect("localhost", { maxRetries: 3 });
+/// // connect("localhost", { maxretries: 3 }); // Throws: unknown field
+/// ```
+#[cfg(feature = "pinar-serde")]
+pub struct Options<T>(pub T);
+
+#[cfg(feature = "pinar-serde")]
+impl<T> Options<T> {
+    /// Returns the inner value
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+#[cfg(feature = "pinar-serde")]
+impl<T> Deref for Options<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+#[cfg(feature = "pinar-serde")]
+impl<T> FromArguments for Options<T>
+where
+    T: serde::de::DeserializeOwned + Default
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        match args.next_arg() {
+            None | Some(JsAny::Undefined(_)) | Some(JsAny::Null(_)) => Ok(Options(T::default())),
+            Some(JsAny::Object(object)) => {
+                crate::pinar_serde::de::from_any(args.env(), JsAny::Object(object))
+                    .map(Options)
+                    .map_err(|e| ArgumentsError::Deserialization(format!("{}", e)).into())
+            }
+            Some(other) => Err(ArgumentsError::unexpected("object", &other, args.arg_number()))
+        }
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.optional(|signature| {
+            signature.push(ArgSignature::new("object", |any| matches!(any, JsAny::Object(_))));
+        });
+    }
 }
 
 impl<A> FromArguments for Vec<A>
@@ -137,21 +505,301 @@ where
 {
     fn from_args(args: &Arguments) -> JsResult<Self> {
         match args.next_arg() {
-            Some(JsAny::Array(array)) => {
-                let args = Arguments {
-                    args: array.values()?,
-                    current_arg: Cell::new(0),
-                    env: args.env(),
-                    this: args.this()?.get_value()
-                };
-
-                (0..array.len()?).map(|_| A::from_args(&args))
-                                 .collect()
+            Some(JsAny::Array(array)) => from_values(args, array.values()?),
+            Some(other) => Err(ArgumentsError::unexpected("array", &other, args.arg_number())),
+            _ => Err(ArgumentsError::missing(args.arg_number()))
+        }
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.push(ArgSignature::new("array", |any| matches!(any, JsAny::Array(_))));
+    }
+}
+
+impl<A> FromArguments for VecDeque<A>
+where
+    A: FromArguments
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        Vec::from_args(args).map(Into::into)
+    }
+
+    fn signature(signature: &mut Signature) {
+        Vec::<A>::signature(signature)
+    }
+}
+
+impl<A> FromArguments for Box<[A]>
+where
+    A: FromArguments
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        Vec::from_args(args).map(Vec::into_boxed_slice)
+    }
+
+    fn signature(signature: &mut Signature) {
+        Vec::<A>::signature(signature)
+    }
+}
+
+/// Converts each value of `values` to `A`
+fn from_values<A: FromArguments>(args: &Arguments, values: Vec<Value>) -> JsResult<Vec<A>> {
+    let len = values.len();
+    let values = Arguments {
+        args: values,
+        current_arg: Cell::new(0),
+        env: args.env(),
+        this: args.this()?.get_value()
+    };
+
+    (0..len).map(|_| A::from_args(&values))
+            .collect()
+}
+
+/// Receives any javascript iterable: arrays, Sets, generators, ..
+///
+/// The iterable is consumed with its `[Symbol.iterator]` method and
+/// each value is converted to `T`.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn sum(numbers: Iterable<i64>) -> i64 {
+///     numbers.iter().sum()
+/// }
+/// // From javascript:
+/// // sum(new Set([1, 2, 3])) // 6
+/// // sum([1, 2, 3].values()) // 6
+/// ```
+pub struct Iterable<T>(pub Vec<T>);
+
+impl<T> Iterable<T> {
+    /// Returns the inner `Vec`
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Iterable<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> Iterable<T> {
+    /// Checks if the value has a `[Symbol.iterator]` method
+    fn is_iterable(any: &JsAny) -> bool {
+        match any {
+            JsAny::Array(_) => true,
+            JsAny::Object(object) => {
+                object.env()
+                      .well_known_symbol("iterator")
+                      .and_then(|symbol| object.get(symbol))
+                      .map(|method| matches!(method, JsAny::Function(_)))
+                      .unwrap_or(false)
+            }
+            _ => false
+        }
+    }
+}
+
+impl<T> FromArguments for Iterable<T>
+where
+    T: FromArguments
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        match args.next_arg() {
+            Some(JsAny::Array(array)) => from_values(args, array.values()?).map(Iterable),
+            Some(ref any @ JsAny::Object(_)) if Self::is_iterable(any) => {
+                let values = any.as_jsobject()?
+                                .iter_js()?
+                                .map(|value| value.map(|value| value.get_value()))
+                                .collect::<JsResult<_>>()?;
+                from_values(args, values).map(Iterable)
             }
-            Some(_) => Err(ArgumentsError::wrong_type("array", args.arg_number())),
+            Some(other) => Err(ArgumentsError::unexpected("iterable", &other, args.arg_number())),
             _ => Err(ArgumentsError::missing(args.arg_number()))
         }
     }
+
+    fn signature(signature: &mut Signature) {
+        signature.push(ArgSignature::new("iterable", Self::is_iterable));
+    }
+}
+
+macro_rules! from_args_collection {
+    (
+        $( ( $jstype:ident, $wrapper:ident, $str:expr ) ),*
+    ) => {
+        $(
+            impl<'e> $jstype<'e> {
+                /// Checks if the argument is a `Map`/`Set`
+                fn is_instance(any: &JsAny) -> bool {
+                    match any {
+                        JsAny::Object(object) => {
+                            matches!($jstype::from_object(object), Ok(Some(_)))
+                        }
+                        _ => false
+                    }
+                }
+            }
+
+            impl<'e> FromArguments for $jstype<'e>
+            {
+                fn from_args(args: &Arguments) -> JsResult<Self> {
+                    match args.next_arg() {
+                        Some(JsAny::Object(object)) => match $jstype::from_object(&object)? {
+                            Some(value) => Ok(value),
+                            _ => Err(ArgumentsError::unexpected($str, &JsAny::Object(object), args.arg_number()))
+                        }
+                        Some(other) => Err(ArgumentsError::unexpected($str, &other, args.arg_number())),
+                        _ => Err(ArgumentsError::missing(args.arg_number()))
+                    }
+                }
+
+                fn signature(signature: &mut Signature) {
+                    signature.push(ArgSignature::new($str, $jstype::is_instance));
+                }
+            }
+
+            impl<T> FromArguments for $wrapper<T>
+            where
+                for<'e> $jstype<'e>: ToRust<T>
+            {
+                fn from_args(args: &Arguments) -> JsResult<Self> {
+                    let collection = $jstype::from_args(args)?;
+                    collection.to_rust()
+                              .map($wrapper)
+                              .map_err(|e| ArgumentsError::locate(e, args.arg_number()))
+                }
+
+                fn signature(signature: &mut Signature) {
+                    $jstype::signature(signature)
+                }
+            }
+        )*
+    }
+}
+
+from_args_collection!(
+    (JsMap, AsMap, "Map"),
+    (JsSet, AsSet, "Set")
+);
+
+/// Receives all the remaining arguments of a function call.
+///
+/// Each argument is converted to `T`.  
+/// This is the equivalent of the javascript rest parameter: `function (...items)`
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn sum(first: i64, others: Rest<i64>) -> i64 {
+///     first + others.iter().sum::<i64>()
+/// }
+/// // From javascript:
+/// // sum(1, 2, 3, 4) // 10
+/// ```
+pub struct Rest<T>(pub Vec<T>);
+
+impl<T> Rest<T> {
+    /// Returns the inner `Vec`
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> Deref for Rest<T> {
+    type Target = Vec<T>;
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> FromArguments for Rest<T>
+where
+    T: FromArguments
+{
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        let mut values = Vec::with_capacity(args.len().saturating_sub(args.arg_number()));
+
+        while args.arg_number() < args.len() {
+            let current = args.arg_number();
+            values.push(T::from_args(args)?);
+            // Avoid an infinite loop with types that don't consume arguments
+            if current == args.arg_number() {
+                break;
+            }
+        }
+
+        Ok(Rest(values))
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.rest(T::signature);
+    }
+}
+
+/// Gives access to all the arguments of the function call, without conversion.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(args: RawArgs) -> JsResult<()> {
+///     // arguments.length
+///     let length = args.len();
+///
+///     for arg in args.iter() {
+///         // ...
+///     }
+///     Ok(())
+/// }
+/// ```
+pub struct RawArgs<'e> {
+    args: Vec<JsAny<'e>>
+}
+
+impl<'e> RawArgs<'e> {
+    /// Returns the number of arguments, similar to `arguments.length` in javascript
+    pub fn len(&self) -> usize {
+        self.args.len()
+    }
+
+    /// Returns true if the function has been called without argument
+    pub fn is_empty(&self) -> bool {
+        self.args.is_empty()
+    }
+
+    /// Returns the argument at `index`
+    pub fn get(&self, index: usize) -> Option<JsAny<'e>> {
+        self.args.get(index).map(JsAny::clone)
+    }
+
+    /// Returns an iterator over the arguments
+    pub fn iter(&self) -> std::slice::Iter<'_, JsAny<'e>> {
+        self.args.iter()
+    }
+
+    /// Returns the arguments as a `Vec`
+    pub fn into_vec(self) -> Vec<JsAny<'e>> {
+        self.args
+    }
+}
+
+impl<'e> FromArguments for RawArgs<'e> {
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        let raw = (0..args.len()).filter_map(|index| args.get(index)).collect();
+        args.consume_all();
+        Ok(RawArgs { args: raw })
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.rest(|signature| signature.push(ArgSignature::any()));
+    }
 }
 
 #[cfg(feature = "json")]
@@ -179,12 +827,16 @@ impl FromArguments for Env {
     fn from_args(args: &Arguments) -> JsResult<Self> {
         Ok(args.env)
     }
+
+    fn signature(_: &mut Signature) {}
 }
 
 impl<'e> FromArguments for JsThis<'e> {
     fn from_args(args: &Arguments) -> JsResult<Self> {
         args.this()
     }
+
+    fn signature(_: &mut Signature) {}
 }
 
 macro_rules! from_args_js {
@@ -200,10 +852,14 @@ macro_rules! from_args_js {
                 fn from_args(args: &Arguments) -> JsResult<Self> {
                     match args.next_arg() {
                         Some(JsAny::$utype(value)) => Ok(value),
-                        Some(_) => Err(ArgumentsError::wrong_type($str, args.arg_number())),
+                        Some(other) => Err(ArgumentsError::unexpected($str, &other, args.arg_number())),
                         _ => Err(ArgumentsError::missing(args.arg_number()))
                     }
                 }
+
+                fn signature(signature: &mut Signature) {
+                    signature.push(ArgSignature::new($str, |any| matches!(any, JsAny::$utype(_))));
+                }
             }
         )*
         $(
@@ -212,10 +868,14 @@ macro_rules! from_args_js {
                 fn from_args(args: &Arguments) -> JsResult<Self> {
                     match args.next_arg() {
                         Some(JsAny::$rutype(value)) => value.to_rust(),
-                        Some(_) => Err(ArgumentsError::wrong_type($rstr, args.arg_number())),
+                        Some(other) => Err(ArgumentsError::unexpected($rstr, &other, args.arg_number())),
                         _ => Err(ArgumentsError::missing(args.arg_number()))
                     }
                 }
+
+                fn signature(signature: &mut Signature) {
+                    signature.push(ArgSignature::new($rstr, |any| matches!(any, JsAny::$rutype(_))));
+                }
             }
         )*
     }
@@ -235,11 +895,29 @@ from_args_js!(
     (JsBoolean, Boolean, "boolean"),
     (JsBigInt, BigInt, "bigint"),
     RUST_TYPES:
+    (i8, Number, "number (integer)"),
+    (i16, Number, "number (integer)"),
     (i32, Number, "number (integer)"),
     (i64, Number, "number (integer)"),
+    (i128, Number, "number (integer)"),
+    (isize, Number, "number (integer)"),
+    (u8, Number, "number (integer)"),
+    (u16, Number, "number (integer)"),
+    (u32, Number, "number (integer)"),
+    (u64, Number, "number (integer)"),
+    (u128, Number, "number (integer)"),
+    (usize, Number, "number (integer)"),
+    (f32, Number, "number (double)"),
     (f64, Number, "number (double)"),
+    (Duration, Number, "number (milliseconds)"),
     (String, String, "string"),
+    (char, String, "string (1 character)"),
     (PathBuf, String, "string"),
+    (OsString, String, "string"),
+    (IpAddr, String, "string (IP address)"),
+    (Ipv4Addr, String, "string (IPv4 address)"),
+    (Ipv6Addr, String, "string (IPv6 address)"),
+    (SocketAddr, String, "string (socket address)"),
     (bool, Boolean, "boolean"),
     (Box, External, "external (box)", T),
     (Rc, External, "external (rc)", T),
@@ -0,0 +1,354 @@
+use std::cell::RefCell;
+use std::ffi::c_void;
+use std::mem::ManuallyDrop;
+use std::panic::AssertUnwindSafe;
+use std::pin::Pin;
+use std::rc::{Rc, Weak};
+use std::sync::OnceLock;
+
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use futures::executor::LocalPool;
+use futures::future::{BoxFuture, FutureExt};
+use futures::stream::{Stream, StreamExt};
+use futures::task::LocalSpawnExt;
+use napi_sys::*;
+
+use crate::Error;
+use crate::error::JsFunctionError;
+use crate::iterator::JsIterator;
+use crate::promise::Deferred;
+use crate::prelude::*;
+
+type Convert = Box<dyn FnOnce(Env) -> JsResult<Value> + Send>;
+type BoxStream = Pin<Box<dyn Stream<Item = Result<Convert, Rejection>> + Send>>;
+
+/// A Rust `Stream` converted to a Javascript async iterator.
+///
+/// The Javascript object implements the async iterator protocol (`next()`
+/// and `return()`) and is async iterable (`[Symbol.asyncIterator]`), so it
+/// can be used with `for await...of`.
+///
+/// The streams of all the `JsAsyncIterator` are polled concurrently on a
+/// single worker thread, started on the first `next()`: a stream must not
+/// block (ex: blocking IO), it would delay the other streams.  
+/// The promises returned by `next()` are fulfilled on the main JS thread, in order.
+/// An error yielded by the stream rejects the promise and ends the iterator.
+///
+/// Calling `return()` (ex: `break` in a `for await`) drops the stream once
+/// the pending `next()` are done.
+///
+/// This requires the feature `futures`.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn numbers() -> JsAsyncIterator {
+///     let stream = futures::stream::iter(vec![Ok(1), Ok(2), Ok(3)]);
+///     JsAsyncIterator::new(stream)
+/// }
+///
+/// // From javascript:
+/// // for await (const n of rust.numbers()) { .. }
+/// ```
+pub struct JsAsyncIterator {
+    state: Rc<RefCell<State>>
+}
+
+struct State {
+    /// The stream, until the worker thread is started
+    stream: Option<BoxStream>,
+    worker: Option<Worker>,
+    /// Number of `next()` waiting for the worker
+    pending: usize,
+    done: bool,
+}
+
+struct Worker {
+    sender: UnboundedSender<Deferred>,
+    tsfn: napi_threadsafe_function,
+}
+
+/// Sends the streams to poll to the worker thread
+static EXECUTOR: OnceLock<UnboundedSender<BoxFuture<'static, ()>>> = OnceLock::new();
+
+/// An error of the stream, converted on the worker thread
+struct Rejection {
+    msg: String,
+    code: Option<String>,
+    kind: JsErrorKind,
+}
+
+/// A `next()` settled by the worker thread, sent to the main JS thread
+struct Settled {
+    deferred: Deferred,
+    result: Result<Option<Convert>, Rejection>,
+}
+
+/// The threadsafe function owned by the worker thread
+struct ThreadSafeFunction(napi_threadsafe_function);
+
+unsafe impl Send for ThreadSafeFunction {}
+
+impl JsAsyncIterator {
+    /// Creates a `JsAsyncIterator` from a `Stream`.
+    ///
+    /// The stream is polled on the worker thread shared by all the
+    /// `JsAsyncIterator`, it must not block.  
+    /// The items are converted to Javascript on the main JS thread.
+    pub fn new<S, T>(stream: S) -> JsAsyncIterator
+    where
+        S: Stream<Item = JsResult<T>> + Send + 'static,
+        T: for<'env> ToJs<'env> + Send + 'static
+    {
+        let stream = stream.map(|item| match item {
+            Ok(value) => Ok(Box::new(move |env: Env| Ok(value.to_js(env)?.get_value())) as Convert),
+            Err(error) => Err(Rejection::new(&error))
+        });
+
+        JsAsyncIterator {
+            state: Rc::new(RefCell::new(State {
+                stream: Some(Box::pin(stream)),
+                worker: None,
+                pending: 0,
+                done: false,
+            }))
+        }
+    }
+
+    fn next(state: &Rc<RefCell<State>>, env: Env) -> JsResult<Value> {
+        let (deferred, promise) = Deferred::new(env)?;
+        let mut inner = state.try_borrow_mut()
+                             .map_err(|_| JsFunctionError::ClosureRunning)?;
+
+        if !inner.done && inner.worker.is_none() {
+            if let Some(stream) = inner.stream.take() {
+                inner.worker = Some(Worker::start(env, state, stream)?);
+            }
+        }
+
+        let deferred = match inner.worker.as_ref() {
+            Some(worker) => match worker.sender.unbounded_send(deferred) {
+                Ok(_) => {
+                    if inner.pending == 0 {
+                        napi_call!(napi_ref_threadsafe_function(env.env(), worker.tsfn))?;
+                    }
+                    inner.pending += 1;
+                    return Ok(promise);
+                }
+                // The stream has been dropped
+                Err(error) => error.into_inner()
+            },
+            _ => deferred
+        };
+
+        inner.done = true;
+        inner.worker = None;
+        deferred.resolve(env, JsIterator::result(env, None)?.get_value())?;
+        Ok(promise)
+    }
+
+    fn finish(state: &Rc<RefCell<State>>, env: Env) -> JsResult<Value> {
+        let (deferred, promise) = Deferred::new(env)?;
+        {
+            let mut inner = state.try_borrow_mut()
+                                 .map_err(|_| JsFunctionError::ClosureRunning)?;
+            // Dropping the sender stops the worker, which drops the stream
+            inner.done = true;
+            inner.stream = None;
+            inner.worker = None;
+        }
+        deferred.resolve(env, JsIterator::result(env, None)?.get_value())?;
+        Ok(promise)
+    }
+}
+
+impl Worker {
+    fn start(env: Env, state: &Rc<RefCell<State>>, stream: BoxStream) -> JsResult<Worker> {
+        let resource_name = "pinar_async_iterator".to_js(env)?;
+        let context = Weak::into_raw(Rc::downgrade(state)) as *mut c_void;
+        let mut tsfn: napi_threadsafe_function = std::ptr::null_mut();
+
+        let status = napi_call!(napi_create_threadsafe_function(
+            env.env(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            resource_name.get_value().get(),
+            0,
+            1,
+            context,
+            Some(__pinar_async_iterator_finalize),
+            context,
+            Some(__pinar_async_iterator_settle),
+            &mut tsfn
+        ));
+        if let Err(status) = status {
+            drop(unsafe { Weak::from_raw(context as *const RefCell<State>) });
+            return Err(status.into());
+        }
+
+        let (sender, receiver) = unbounded();
+        let function = ThreadSafeFunction(tsfn);
+        // The task is dropped with the stream if the worker thread has exited:
+        // the next `next()` ends the iterator
+        let _ = executor().unbounded_send(poll_stream(function, stream, receiver).boxed());
+
+        Ok(Worker { sender, tsfn })
+    }
+}
+
+/// Returns the sender of the worker thread, starting it on the first call
+fn executor() -> &'static UnboundedSender<BoxFuture<'static, ()>> {
+    EXECUTOR.get_or_init(|| {
+        let (sender, mut receiver) = unbounded::<BoxFuture<'static, ()>>();
+
+        std::thread::spawn(move || {
+            let mut pool = LocalPool::new();
+            let spawner = pool.spawner();
+            pool.run_until(async move {
+                while let Some(task) = receiver.next().await {
+                    let _ = spawner.spawn_local(task);
+                }
+            });
+        });
+
+        sender
+    })
+}
+
+/// Polls the stream for each `next()` until the iterator is dropped or returned
+async fn poll_stream(function: ThreadSafeFunction, stream: BoxStream, mut receiver: UnboundedReceiver<Deferred>) {
+    let mut stream = Some(stream);
+
+    while let Some(deferred) = receiver.next().await {
+        let result = match stream.as_mut() {
+            Some(stream) => match AssertUnwindSafe(stream.next()).catch_unwind().await {
+                Ok(Some(Ok(value))) => Ok(Some(value)),
+                Ok(Some(Err(rejection))) => Err(rejection),
+                Ok(None) => Ok(None),
+                Err(_) => Err(Rejection::panicked()),
+            },
+            _ => Ok(None)
+        };
+        if !matches!(result, Ok(Some(_))) {
+            stream = None;
+        }
+        function.call(Settled { deferred, result });
+    }
+}
+
+impl ThreadSafeFunction {
+    fn call(&self, settled: Settled) {
+        let data = Box::into_raw(Box::new(settled));
+        let status = napi_call!(napi_call_threadsafe_function(
+            self.0,
+            data as *mut c_void,
+            napi_threadsafe_function_call_mode::napi_tsfn_blocking
+        ));
+        if status.is_err() {
+            drop(unsafe { Box::from_raw(data) });
+        }
+    }
+}
+
+impl Drop for ThreadSafeFunction {
+    fn drop(&mut self) {
+        let _ = napi_call!(napi_release_threadsafe_function(
+            self.0,
+            napi_threadsafe_function_release_mode::napi_tsfn_release
+        ));
+    }
+}
+
+impl Rejection {
+    fn new(error: &Error) -> Rejection {
+        let error = error.as_js_error();
+        Rejection {
+            msg: error.get_msg(),
+            code: error.get_code(),
+            kind: error.get_kind(),
+        }
+    }
+
+    fn panicked() -> Rejection {
+        Rejection {
+            msg: "Rust has panicked while polling the stream".to_owned(),
+            code: Some("PINAR".to_owned()),
+            kind: JsErrorKind::Error,
+        }
+    }
+
+    fn to_js(&self, env: Env) -> JsResult<Value> {
+        env.create_error(&self.msg, self.code.as_deref(), self.kind)
+    }
+}
+
+impl Settled {
+    fn settle(self, env: Env) -> JsResult<()> {
+        let value = match self.result {
+            Ok(Some(convert)) => convert(env),
+            Ok(None) => return self.deferred.resolve(env, JsIterator::result(env, None)?.get_value()),
+            Err(rejection) => return self.deferred.reject(env, rejection.to_js(env)?),
+        };
+        match value {
+            Ok(value) => self.deferred.resolve(env, JsIterator::result(env, Some(value))?.get_value()),
+            Err(error) => self.deferred.reject(env, env.error_to_js(&error)?),
+        }
+    }
+}
+
+/// Function executed on the main JS thread, with the result of a `next()`
+extern "C" fn __pinar_async_iterator_settle(
+    env: napi_env,
+    _js_callback: napi_value,
+    context: *mut c_void,
+    data: *mut c_void,
+) {
+    let settled = unsafe { Box::from_raw(data as *mut Settled) };
+    if env.is_null() {
+        return;
+    }
+    let env = Env::from(env);
+
+    let state = ManuallyDrop::new(unsafe { Weak::from_raw(context as *const RefCell<State>) });
+    if let Some(state) = state.upgrade() {
+        if let Ok(mut inner) = state.try_borrow_mut() {
+            inner.pending = inner.pending.saturating_sub(1);
+            if !matches!(settled.result, Ok(Some(_))) {
+                inner.done = true;
+                inner.worker = None;
+            }
+            // Let the JS loop exit when no promise is waiting for the stream
+            if let (0, Some(worker)) = (inner.pending, inner.worker.as_ref()) {
+                let _ = napi_call!(napi_unref_threadsafe_function(env.env(), worker.tsfn));
+            }
+        }
+    }
+
+    let _ = settled.settle(env);
+}
+
+unsafe extern "C" fn __pinar_async_iterator_finalize(_env: napi_env, data: *mut c_void, _finalize_hint: *mut c_void) {
+    drop(Weak::from_raw(data as *const RefCell<State>));
+}
+
+impl<'e> ToJs<'e> for JsAsyncIterator {
+    type Value = JsObject<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsObject<'e>> {
+        let object = env.object()?;
+
+        let state = Rc::clone(&self.state);
+        let next = JsClosure::new(move |env: Env| JsAsyncIterator::next(&state, env));
+
+        let state = Rc::clone(&self.state);
+        let ret = JsClosure::new(move |env: Env| JsAsyncIterator::finish(&state, env));
+
+        let iterator = JsClosure::new(|this: JsThis| this.get_value());
+
+        object.set("next", next)?;
+        object.set("return", ret)?;
+        object.set(env.well_known_symbol("asyncIterator")?, iterator)?;
+
+        Ok(object)
+    }
+}
@@ -3,10 +3,16 @@ use std::rc::Rc;
 use std::ffi::c_void;
 use napi_sys::*;
 use std::ffi::CString;
-use std::any::TypeId;
+use std::any::{Any, TypeId};
+use std::cell::{Ref, RefCell, RefMut};
+use std::collections::HashMap;
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
 
 use crate::prelude::*;
-use crate::error::JsClassError;
+use crate::error::{JsClassError, JsErrorKind};
+use crate::arguments::{ArgSignature, Signature};
+use crate::module::{ModuleFunction, CallbackFn};
 
 /// Trait to implement to create a Javascript class.
 ///
@@ -81,8 +87,6 @@ pub trait JsClass : Sized + 'static {
 
 /// Private trait implemented on types that implement `JsClass`
 trait JsClassInternal {
-    const CLASS_DATA: &'static str;
-    const PINAR_CLASS_ID: &'static str;
 
     /// Function to construct the js class
     extern "C" fn __pinar_class_constructor(env: napi_env, cb_info: napi_callback_info) -> napi_value;
@@ -91,6 +95,25 @@ trait JsClassInternal {
     ///
     /// It is responsible of converting values from/to JS and call the appropriate Rust method.
     extern "C" fn __pinar_class_dispatch(env: napi_env, cb_info: napi_callback_info) -> napi_value;
+
+    /// Function called when a setter of the class is called from js.
+    extern "C" fn __pinar_class_dispatch_setter(env: napi_env, cb_info: napi_callback_info) -> napi_value;
+
+    /// Function called when a method returning `this` on non-instances is called from js.
+    extern "C" fn __pinar_class_dispatch_or_this(env: napi_env, cb_info: napi_callback_info) -> napi_value;
+
+    /// Call the method, getter or setter at the index given in the callback data.
+    fn dispatch(env: napi_env, cb_info: napi_callback_info, kind: DispatchKind) -> napi_value;
+}
+
+/// Handler called by the dispatch function
+#[derive(Clone, Copy, PartialEq)]
+enum DispatchKind {
+    /// A method or a getter
+    Method,
+    Setter,
+    /// A method returning `this` when it's not an instance of the class
+    MethodOrThis,
 }
 
 pub(crate) unsafe extern "C" fn __pinar_drop_box<T>(_env: napi_env, data: *mut c_void, _finalize_hint: *mut c_void) {
@@ -103,10 +126,6 @@ pub(crate) unsafe extern "C" fn __pinar_drop_rc<T>(_env: napi_env, data: *mut c_
     Rc::<T>::from_raw(data as *mut T);
 }
 
-extern "C" fn __pinar_nop(_env: napi_env, _cb_info: napi_callback_info) -> napi_value {
-    std::ptr::null_mut()
-}
-
 /// Run Rust code and handle result correctly.
 #[inline(always)]
 pub(crate) fn execute_safely<F>(env: napi_env, closure: F) -> napi_value
@@ -120,7 +139,13 @@ where
         Ok(Err(error)) => {
             let env = Env::from(env);
             let e = error.as_js_error();
-            env.throw_error(format!("{}\n{:?}", e.get_msg(), error.backtrace()), e.get_code()).ok();
+            let msg = format!("{}\n{:?}", e.get_msg(), error.backtrace());
+            let code = e.get_code();
+            match e.get_kind() {
+                JsErrorKind::Error => env.throw_error(msg, code),
+                JsErrorKind::TypeError => env.throw_type_error(msg, code.as_deref()),
+                JsErrorKind::RangeError => env.throw_range_error(msg, code.as_deref()),
+            }.ok();
             std::ptr::null_mut()
         }
         Err(_) => {
@@ -139,8 +164,6 @@ where
 }
 
 impl<C: 'static +  JsClass> JsClassInternal for C {
-    const CLASS_DATA: &'static str = "__pinar_class_data__";
-    const PINAR_CLASS_ID: &'static str = "___pinar___class___id___";
 
     extern "C" fn __pinar_class_constructor(
         env: napi_env,
@@ -151,49 +174,50 @@ impl<C: 'static +  JsClass> JsClassInternal for C {
 
         execute_safely(env, || {
             let env = Env::from(env);
+
+            // new.target is the JS subclass when called with super(..)
+            if env.new_target(cb_info)?.is_none() {
+                return Err(ThisConstructor(C::CLASSNAME).into())
+            }
+
             let (class_data, args) = env.callback_info::<JsClassData<Self>>(cb_info)?;
-            let mut class_data = unsafe { Rc::from_raw(class_data) };
 
-            let class = if class_data.args_rust.is_some() {
+            // The pointer is owned by the JS constructor, take a new reference:
+            // 1 JsClassData for each JS instance, + 1 for the JS constructor
+            let class_data = unsafe { Rc::from_raw(class_data) };
+            let copy_class_data = Rc::clone(&class_data);
+            std::mem::forget(class_data);
+
+            let pending = copy_class_data.pending.borrow_mut().take();
+            let class = match pending {
                 // Constructor has been called from Rust
-                let args_rust = Rc::make_mut(&mut class_data).args_rust.take().expect("None value here");
-                Self::constructor(args_rust)?
-            } else if class_data.instance.is_some() {
+                Some(Pending::Args(args_rust)) => Self::constructor(args_rust)?,
                 // Constructor has been called with the existing Rust instance
-                Rc::make_mut(&mut class_data).instance.take().expect("Instance none")
-            } else {
+                Some(Pending::Instance(instance)) => instance,
                 // Constructor has been called from JS
-                Self::constructor(FromArguments::from_args(&args)?)?
+                None => Self::constructor(FromArguments::from_args(&args)?)?
             };
 
-            // 1 JsClassData for each JS instance, + 1 for the JS constructor
-            let copy_class_data = Rc::clone(&class_data);
-            std::mem::forget(class_data);
-
             let this = args.this()?
                            .as_jsobject()
                            .map_err(|_| ThisConstructor(C::CLASSNAME))?;
 
-            if !(this.has_property(C::PINAR_CLASS_ID)?) {
-                return Err(ThisConstructor(C::CLASSNAME).into())
-            }
-
-            let class = Box::new(class);
-
-            this.define_property(PropertyDescriptor::value(
-                env,
-                Self::CLASS_DATA,
-                copy_class_data
-            )?)?;
+            let instance = Box::new(Instance {
+                id: TypeId::of::<C>(),
+                data: copy_class_data,
+                value: RefCell::new(class)
+            });
 
             napi_call!(napi_wrap(
                 env.env(),
                 this.get_value().value,
-                Box::into_raw(class) as *mut c_void,
-                Some( __pinar_drop_box::<C>),
+                Box::into_raw(instance) as *mut c_void,
+                Some( __pinar_drop_box::<Instance<C>>),
                 std::ptr::null_mut(),
                 std::ptr::null_mut()
             ))?;
+            #[cfg(feature = "napi8")]
+            this.type_tag(TypeTag::of::<Instance<C>>())?;
 
             Ok(Some(this.get_value()))
         })
@@ -204,117 +228,428 @@ impl<C: 'static +  JsClass> JsClassInternal for C {
         cb_info: napi_callback_info
     ) -> napi_value
     {
+        Self::dispatch(env, cb_info, DispatchKind::Method)
+    }
+
+    extern "C" fn __pinar_class_dispatch_setter(
+        env: napi_env,
+        cb_info: napi_callback_info
+    ) -> napi_value
+    {
+        Self::dispatch(env, cb_info, DispatchKind::Setter)
+    }
+
+    extern "C" fn __pinar_class_dispatch_or_this(
+        env: napi_env,
+        cb_info: napi_callback_info
+    ) -> napi_value
+    {
+        Self::dispatch(env, cb_info, DispatchKind::MethodOrThis)
+    }
+
+    fn dispatch(env: napi_env, cb_info: napi_callback_info, kind: DispatchKind) -> napi_value {
         use self::JsClassError::*;
 
         execute_safely(env, || {
             let env = Env::from(env);
             let (key, args) = env.callback_info::<usize>(cb_info)?;
 
-            let this = args.this()?
-                           .as_jsobject()
-                           .map_err(|_| ThisMethod(C::CLASSNAME))?;
-
-            let external = this.get(Self::CLASS_DATA)?
-                               .as_jsexternal()
-                               .map_err(|_| ExternalClassData)?;
+            let or_this = kind == DispatchKind::MethodOrThis;
+            let this = match args.this()?.as_jsobject() {
+                Ok(this) => this,
+                Err(_) if or_this => return Ok(Some(args.this()?.get_value())),
+                Err(_) => return Err(ThisMethod(C::CLASSNAME).into())
+            };
 
-            let this = this.napi_unwrap::<Self>()?;
-            let class_data = external.get_rc::<JsClassData<Self>>()?;
+            let instance = match unwrap_instance::<Self>(&this) {
+                Some(instance) => unsafe { &*instance },
+                _ if or_this => return Ok(Some(this.get_value())),
+                _ => return Err(WrongClass.into())
+            };
 
-            if class_data.id != TypeId::of::<Self>() {
-                return Err(WrongClass.into());
-            }
+            let class_data = &instance.data;
+            let handlers = match kind {
+                DispatchKind::Setter => &class_data.setters,
+                _ => &class_data.methods
+            };
 
-            match class_data.methods.get(key as usize) {
-                Some(method) => method.call(unsafe { &mut *this }, &args),
+            match handlers.get(key as usize) {
+                Some(Some(method)) => method.call(&instance.value, &args),
                 _ => Err(WrongHandler.into())
             }
         })
     }
 }
 
-/// Class Data attached to the JS instance
-struct JsClassData<C: JsClass> {
+/// Native instance wrapped in the JS object
+#[repr(C)]
+struct Instance<C: JsClass> {
+    /// Must be the first field: it's read before knowing the class
     id: TypeId,
-    args_rust: Option<C::ArgsConstructor>,
-    methods: Vec<Rc<dyn ClassMethodHandler<C>>>,
-    instance: Option<C>
+    data: Rc<JsClassData<C>>,
+    value: RefCell<C>
 }
 
-// Implement Clone ourself because ArgsConstructor might not be clonable
-impl<C: JsClass> Clone for JsClassData<C> {
-    fn clone(&self) -> Self {
-        JsClassData {
-            id: self.id,
-            args_rust: None,
-            methods: self.methods.clone(),
-            instance: None
+/// Returns the Rust instance wrapped in `object`, or `None` if the object
+/// is not an instance of the class `C`.
+///
+/// The instances are identified by their type tag, or by the type id at
+/// the start of the wrapped data without the `napi8` feature.
+#[cfg(feature = "napi8")]
+fn unwrap_instance<C: JsClass>(object: &JsObject) -> Option<*mut Instance<C>> {
+    match object.has_type_tag(TypeTag::of::<Instance<C>>()) {
+        Ok(true) => object.napi_unwrap::<Instance<C>>(),
+        _ => None
+    }
+}
+
+#[cfg(not(feature = "napi8"))]
+fn unwrap_instance<C: JsClass>(object: &JsObject) -> Option<*mut Instance<C>> {
+    match object.napi_unwrap::<TypeId>() {
+        Some(id) if unsafe { *id } == TypeId::of::<C>() => Some(id as *mut Instance<C>),
+        _ => None
+    }
+}
+
+/// Value of an instance, its class is unknown
+type AnyInstance = dyn Any;
+
+/// Casts the value of an instance to one of its ancestors
+#[derive(Clone)]
+struct Upcaster {
+    shared: Rc<dyn Fn(&AnyInstance) -> &AnyInstance>,
+    mutable: Rc<dyn Fn(&mut AnyInstance) -> &mut AnyInstance>
+}
+
+impl Upcaster {
+    /// Casts a `C` to a `P`
+    fn new<C, P>(upcast: fn(&C) -> &P, upcast_mut: fn(&mut C) -> &mut P) -> Upcaster
+    where
+        C: JsClass,
+        P: JsClass
+    {
+        // The values are checked when the upcaster is selected, see `Descendant`
+        Upcaster::from_fns(
+            move |value| upcast(value.downcast_ref::<C>().unwrap()),
+            move |value| upcast_mut(value.downcast_mut::<C>().unwrap())
+        )
+    }
+
+    /// Casts with `first`, then with `self`
+    fn after(&self, first: Upcaster) -> Upcaster {
+        let (shared, mutable) = (Rc::clone(&self.shared), Rc::clone(&self.mutable));
+        let Upcaster { shared: first_shared, mutable: first_mutable } = first;
+        Upcaster::from_fns(
+            move |value| shared(first_shared(value)),
+            move |value| mutable(first_mutable(value))
+        )
+    }
+
+    fn from_fns<S, M>(shared: S, mutable: M) -> Upcaster
+    where
+        S: Fn(&AnyInstance) -> &AnyInstance + 'static,
+        M: Fn(&mut AnyInstance) -> &mut AnyInstance + 'static
+    {
+        Upcaster { shared: Rc::new(shared), mutable: Rc::new(mutable) }
+    }
+}
+
+/// A class inheriting from another class, directly or not
+struct Descendant {
+    class: TypeId,
+    #[cfg(feature = "napi8")]
+    tag: TypeTag,
+    /// Returns the value of an instance of the class
+    value: unsafe fn(*mut c_void) -> *const RefCell<AnyInstance>,
+    /// Casts the value to the ancestor
+    upcast: Upcaster
+}
+
+impl Descendant {
+    fn new<C: JsClass>(upcast: Upcaster) -> Descendant {
+        Descendant {
+            class: TypeId::of::<C>(),
+            #[cfg(feature = "napi8")]
+            tag: TypeTag::of::<Instance<C>>(),
+            value: instance_value::<C>,
+            upcast
+        }
+    }
+
+    /// Returns the instance wrapped in `object` if it's an instance of the class
+    #[cfg(feature = "napi8")]
+    fn unwrap(&self, object: &JsObject) -> Option<*mut c_void> {
+        match object.has_type_tag(self.tag) {
+            Ok(true) => object.napi_unwrap::<c_void>(),
+            _ => None
         }
     }
+
+    #[cfg(not(feature = "napi8"))]
+    fn unwrap(&self, object: &JsObject) -> Option<*mut c_void> {
+        match object.napi_unwrap::<TypeId>() {
+            Some(id) if unsafe { *id } == self.class => Some(id as *mut c_void),
+            _ => None
+        }
+    }
+}
+
+unsafe fn instance_value<C: JsClass>(instance: *mut c_void) -> *const RefCell<AnyInstance> {
+    &(*(instance as *const Instance<C>)).value
+}
+
+/// Returns the value of the instance wrapped in `object`, with the upcast
+/// to `C` when it's an instance of a class inheriting from `C`.
+///
+/// Returns `None` if the object is not an instance of `C` nor of one of
+/// its descendants.
+fn unwrap_class<C: JsClass>(object: &JsObject) -> Option<(*const RefCell<AnyInstance>, Option<Upcaster>)> {
+    if let Some(instance) = unwrap_instance::<C>(object) {
+        return Some((unsafe { instance_value::<C>(instance as *mut c_void) }, None));
+    }
+
+    DESCENDANTS.with(|descendants| {
+        descendants.borrow().get(&TypeId::of::<C>())?.iter().find_map(|descendant| {
+            let instance = descendant.unwrap(object)?;
+            Some((unsafe { (descendant.value)(instance) }, Some(descendant.upcast.clone())))
+        })
+    })
+}
+
+/// Class Data attached to the JS instance
+struct JsClassData<C: JsClass> {
+    /// Methods and getters, indexed by property
+    methods: Vec<Option<Rc<dyn ClassMethodHandler<C>>>>,
+    /// Setters, indexed by property
+    setters: Vec<Option<Rc<dyn ClassMethodHandler<C>>>>,
+    /// Set when the class is instantiated from Rust
+    pending: RefCell<Option<Pending<C>>>
+}
+
+/// Value used by the constructor, instead of the JS arguments
+enum Pending<C: JsClass> {
+    Args(C::ArgsConstructor),
+    Instance(C)
+}
+
+/// Class constructor created for an env
+struct CachedClass {
+    constructor: napi_ref,
+    data: Rc<dyn Any>
+}
+
+thread_local! {
+    /// Constructors of the classes, by env and class
+    static CLASSES: RefCell<HashMap<(usize, TypeId), CachedClass>> = RefCell::new(HashMap::new());
+
+    /// Classes inheriting from a class, by type id of the ancestor
+    static DESCENDANTS: RefCell<HashMap<TypeId, Vec<Descendant>>> = RefCell::new(HashMap::new());
+}
+
+/// Removes the classes of an env when it's destroyed
+unsafe extern "C" fn __pinar_clear_classes(env: *mut c_void) {
+    let removed: Vec<CachedClass> = CLASSES.with(|classes| {
+        let mut classes = classes.borrow_mut();
+        let keys: Vec<_> = classes.keys().filter(|(class_env, _)| *class_env == env as usize).cloned().collect();
+        keys.iter().filter_map(|key| classes.remove(key)).collect()
+    });
+
+    // The env is still usable in its cleanup hooks
+    for class in removed {
+        let _ = napi_call!(napi_delete_reference(env as napi_env, class.constructor));
+    }
 }
 
 /// Struct holding informations (properties) of the JS class to build.
 #[doc(hidden)]
 pub struct ClassBuilder<C: JsClass> {
     props: Vec<ClassProperty<C>>,
+    /// Properties of the parent class, overridden by `props`
+    inherited: Vec<ClassProperty<C>>,
+    statics: Vec<StaticProperty>,
+    /// Creates the parent class
+    parent: Option<fn(Env) -> JsResult<Value>>,
+    /// Upcasts to the parent classes, by type id
+    ancestors: HashMap<TypeId, Upcaster>,
     name: String
 }
 
-struct ClassProperty<C: JsClass> {
+/// Property defined on the class constructor
+struct StaticProperty {
     name: CString,
+    value: Rc<dyn Fn(Env) -> JsResult<Value>>
+}
+
+/// Same layout as `napi_property_descriptor`, with the attributes as an integer.
+///
+/// napi-sys 0.1 declares `napi_property_attributes` as an enum: it can't hold a
+/// combination of attributes.
+#[repr(C)]
+struct RawPropertyDescriptor {
+    utf8name: *const std::os::raw::c_char,
+    name: napi_value,
+    method: napi_callback,
+    getter: napi_callback,
+    setter: napi_callback,
+    value: napi_value,
+    attributes: u32,
+    data: *mut c_void,
+}
+
+/// Key of a class property
+#[derive(Clone, PartialEq)]
+enum PropertyKey {
+    Name(CString),
+    /// A well-known symbol: `Symbol[name]`
+    Symbol(&'static str),
+    /// A symbol of the global registry: `Symbol.for(name)`
+    SymbolFor(&'static str),
+}
+
+impl std::fmt::Display for PropertyKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            PropertyKey::Name(name) => write!(f, "{}", name.to_string_lossy()),
+            PropertyKey::Symbol(name) => write!(f, "[Symbol.{}]", name),
+            PropertyKey::SymbolFor(name) => write!(f, "[Symbol.for('{}')]", name),
+        }
+    }
+}
+
+impl PropertyKey {
+    /// Returns the symbol of the key, `None` for a string key
+    fn symbol(&self, env: &Env) -> JsResult<Option<Value>> {
+        match self {
+            PropertyKey::Name(_) => Ok(None),
+            PropertyKey::Symbol(name) => env.well_known_symbol(name).map(Some),
+            PropertyKey::SymbolFor(name) => env.registered_symbol(name).map(Some),
+        }
+    }
+}
+
+struct ClassProperty<C: JsClass> {
+    name: PropertyKey,
     method: Option<Rc<dyn ClassMethodHandler<C>>>,
-    accessor: Option<Rc<dyn ClassMethodHandler<C>>>,
+    getter: Option<Rc<dyn ClassMethodHandler<C>>>,
+    setter: Option<Rc<dyn ClassMethodHandler<C>>>,
+    /// Read-only value, defined on the prototype
+    value: Option<Rc<dyn Fn(Env) -> JsResult<Value>>>,
+    /// The method returns `this` when it's not called on an instance
+    or_this: bool,
+}
+
+/// What a class property is defined as.
+///
+/// A property descriptor can't mix them: a method or a value can't also
+/// have a getter or a setter.
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PropertyKind {
+    Method,
+    /// A getter and/or a setter
+    Accessor,
+    Value,
 }
 
 impl<C: JsClass + 'static> ClassProperty<C> {
-    pub fn method<S, A, R>(name: S, method: ClassMethod<C, A, R>) -> ClassProperty<C>
-    where
-        S: AsRef<str>,
-        A: FromArguments + 'static,
-        R: for <'env> JsReturn<'env> + 'static,
-    {
+    fn new(name: PropertyKey) -> ClassProperty<C> {
         ClassProperty {
-            name: CString::new(name.as_ref()).unwrap(),
-            method: Some(Rc::new(method)),
-            accessor: None
+            name,
+            method: None,
+            getter: None,
+            setter: None,
+            value: None,
+            or_this: false
         }
     }
 
-    pub fn accessor<S, A, R>(name: S, accessor: ClassMethod<C, A, R>) -> ClassProperty<C>
-    where
-        S: AsRef<str>,
-        A: FromArguments + 'static,
-        R: for <'env> JsReturn<'env> + 'static,
-    {
-        ClassProperty {
-            name: CString::new(name.as_ref()).unwrap(),
-            method: None,
-            accessor: Some(Rc::new(accessor))
+    /// Returns the kind of the property, `None` if nothing is defined yet
+    fn kind(&self) -> Option<PropertyKind> {
+        if self.method.is_some() {
+            Some(PropertyKind::Method)
+        } else if self.getter.is_some() || self.setter.is_some() {
+            Some(PropertyKind::Accessor)
+        } else if self.value.is_some() {
+            Some(PropertyKind::Value)
+        } else {
+            None
         }
     }
 }
 
 impl<C: JsClass> Default for ClassBuilder<C> {
     fn default() -> ClassBuilder<C> {
-        let builder = ClassBuilder { name: C::CLASSNAME.to_owned(), props: vec![] };
+        let builder = ClassBuilder {
+            name: C::CLASSNAME.to_owned(),
+            props: vec![],
+            inherited: vec![],
+            statics: vec![],
+            parent: None,
+            ancestors: HashMap::new()
+        };
         C::default_properties(builder)
     }
 }
 
 impl<C: JsClass + 'static> ClassBuilder<C> {
     /// Add a method to the class
-    pub fn with_method<S, A, R, Method>(mut self, name: S, method: Method) -> Self
+    ///
+    /// Methods taking `&self` can run while another `&self` method of the
+    /// same instance is running (re-entrant calls from JS), methods taking
+    /// `&mut self` have an exclusive access: a conflicting call throws an error.
+    ///
+    /// Classes declared with `#[pinar]` don't share borrows yet: their methods
+    /// are registered as `&mut self` methods by pinar-derive.
+    ///
+    /// Panics if a getter or a setter has the same name.
+    pub fn with_method<S, A, R, K, Method>(mut self, name: S, method: Method) -> Self
+    where
+        S: AsRef<str>,
+        A: FromArguments + 'static,
+        R: for <'env> JsReturn<'env> + 'static,
+        Method: MethodFn<C, A, R, K> + 'static
+    {
+        self.property(name, PropertyKind::Method).method = Some(Rc::new(method.make()));
+        self
+    }
+
+    /// Add a getter to the class
+    ///
+    /// Without a setter, the property is read-only: assigning it throws in strict mode.
+    ///
+    /// Panics if a method has the same name.
+    pub fn with_getter<S, R, K, Getter>(mut self, name: S, getter: Getter) -> Self
+    where
+        S: AsRef<str>,
+        R: for <'env> JsReturn<'env> + 'static,
+        Getter: MethodFn<C, (), R, K> + 'static
+    {
+        self.property(name, PropertyKind::Accessor).getter = Some(Rc::new(getter.make()));
+        self
+    }
+
+    /// Add a setter to the class
+    ///
+    /// It is combined with the getter of the same name, if any.
+    /// Panics if a method has the same name.
+    pub fn with_setter<S, A, R, Setter>(mut self, name: S, setter: Setter) -> Self
     where
         S: AsRef<str>,
         A: FromArguments + 'static,
         R: for <'env> JsReturn<'env> + 'static,
-        Method: MethodFn<C, A, R> + 'static
+        Setter: Fn(&mut C, A) -> R + 'static
     {
-        self.props.push(ClassProperty::method(name, method.make()));
+        self.property(name, PropertyKind::Accessor).setter = Some(Rc::new(ClassMethod::new(setter)));
         self
     }
 
     /// Add an accessor to the class
+    ///
+    /// The same function is used as getter and setter, it receives `None` when
+    /// the property is read.  
+    /// Prefer [`with_getter`] and [`with_setter`].
+    ///
+    /// [`with_getter`]: #method.with_getter
+    /// [`with_setter`]: #method.with_setter
     pub fn with_accessor<S, A, R, Accessor>(mut self, name: S, accessor: Accessor) -> Self
     where
         S: AsRef<str>,
@@ -322,54 +657,296 @@ impl<C: JsClass + 'static> ClassBuilder<C> {
         R: for <'env> JsReturn<'env> + 'static,
         Accessor: Fn(&mut C, Option<A>) -> R + 'static
     {
-        self.props.push(ClassProperty::accessor(name, ClassMethod::new(accessor)));
+        let accessor: Rc<dyn ClassMethodHandler<C>> = Rc::new(ClassMethod::new(accessor));
+        let prop = self.property(name, PropertyKind::Accessor);
+        prop.getter = Some(Rc::clone(&accessor));
+        prop.setter = Some(accessor);
         self
     }
 
-    /// Build the class with its properties
-    fn create_internal<'e>(
-        &self,
-        env: &Env,
-        args_rust: Option<C::ArgsConstructor>,
-        instance: Option<C>
-    ) -> JsResult<JsFunction<'e>>
+    /// Defines `toString()` with the `Display` implementation of the class
+    pub fn with_to_string(self) -> Self
+    where
+        C: std::fmt::Display
     {
-        let mut props: Vec<_> = self.props.iter().enumerate().map(|(index, prop)| { napi_property_descriptor {
-            utf8name: prop.name.as_ptr() as *const i8,
-            name: std::ptr::null_mut(),
-            method: if prop.method.is_some() { Some(C::__pinar_class_dispatch) } else { None },
-            getter: if prop.accessor.is_some() { Some(C::__pinar_class_dispatch) } else { None },
-            setter: if prop.accessor.is_some() { Some(C::__pinar_class_dispatch) } else { None },
-            value: std::ptr::null_mut(),
-            attributes: napi_property_attributes::napi_default,
-            data: index as *mut std::ffi::c_void,
-        }
+        self.with_method("toString", |this: &C| this.to_string())
+    }
+
+    /// Defines how Node displays the instances (`console.log`, `util.inspect`)
+    /// with the `Debug` implementation of the class
+    pub fn with_inspect(mut self) -> Self
+    where
+        C: std::fmt::Debug
+    {
+        let inspect = ClassMethod::new_shared(|this: &C, ()| format!("{:?}", this));
+        let prop = self.property_key(PropertyKey::SymbolFor("nodejs.util.inspect.custom"), PropertyKind::Method);
+        prop.method = Some(Rc::new(inspect));
+        // On the prototype or any non-instance, `this` is returned and
+        // Node falls back to the default inspection
+        prop.or_this = true;
+        self
+    }
+
+    /// Defines `toJSON()`, used by `JSON.stringify`, with the `Serialize`
+    /// implementation of the class
+    #[cfg(feature = "pinar-serde")]
+    pub fn with_to_json(self) -> Self
+    where
+        C: serde::Serialize
+    {
+        self.with_method("toJSON", |this: &C, env: Env| -> JsResult<Value> {
+            Ok(crate::pinar_serde::ser::serialize_to_js(env, this)?)
+        })
+    }
+
+    /// Defines `[Symbol.toStringTag]`, used by `Object.prototype.toString`
+    ///
+    /// The tag is a read-only value of the prototype.
+    ///
+    /// ```
+    /// builder.with_to_string_tag("MyStruct")
+    ///
+    /// // From javascript:
+    /// // Object.prototype.toString.call(instance) // '[object MyStruct]'
+    /// ```
+    pub fn with_to_string_tag<S: Into<String>>(mut self, tag: S) -> Self {
+        let tag = tag.into();
+        let value = move |env: Env| tag.to_js(env).map(|v| v.get_value());
+        self.property_key(PropertyKey::Symbol("toStringTag"), PropertyKind::Value).value = Some(Rc::new(value));
+        self
+    }
+
+    /// Makes the instances iterable (`for...of`, spread syntax).
+    ///
+    /// `fun` returns the Rust iterator of an instance, converted to a
+    /// [`JsIterator`]. The iterator must own its items: it can outlive
+    /// the call.
+    ///
+    /// ```
+    /// builder.with_iterator(|this: &MyList| this.items.clone())
+    ///
+    /// // From javascript:
+    /// // for (const item of list) { .. }
+    /// ```
+    ///
+    /// [`JsIterator`]: ./struct.JsIterator.html
+    pub fn with_iterator<F, I>(mut self, fun: F) -> Self
+    where
+        F: Fn(&C) -> I + 'static,
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: for<'env> ToJs<'env> + 'static
+    {
+        let iterator = ClassMethod::new_shared(move |this: &C, ()| JsIterator::new(fun(this)));
+        self.property_key(PropertyKey::Symbol("iterator"), PropertyKind::Method).method = Some(Rc::new(iterator));
+        self
+    }
+
+    /// Inherit the properties of the class `P`.
+    ///
+    /// The Rust struct contains its parent, `upcast` and `upcast_mut` return it.  
+    /// The methods, getters and setters of `P` are available on instances of
+    /// `C`, unless `C` defines a property of the same name.  
+    /// Instances of `C` are accepted as [`ClassRef`], [`ClassBorrow`] and
+    /// [`ClassBorrowMut`] of `P` and of its own parents.  
+    /// In JS, the prototype chain is set: static properties of `P` are
+    /// reachable from the class `C`.
+    ///
+    /// ```
+    /// struct Animal { name: String }
+    /// struct Dog { animal: Animal }
+    ///
+    /// impl JsClass for Dog {
+    ///     ...
+    ///     fn default_properties(builder: ClassBuilder<Self>) -> ClassBuilder<Self> {
+    ///         builder.with_parent::<Animal>(|dog| &dog.animal, |dog| &mut dog.animal)
+    ///                .with_method("bark", Dog::bark)
+    ///     }
+    /// }
+    /// ```
+    ///
+    /// [`ClassRef`]: ./struct.ClassRef.html
+    /// [`ClassBorrow`]: ./struct.ClassBorrow.html
+    /// [`ClassBorrowMut`]: ./struct.ClassBorrowMut.html
+    pub fn with_parent<P>(mut self, upcast: fn(&C) -> &P, upcast_mut: fn(&mut C) -> &mut P) -> Self
+    where
+        P: JsClass
+    {
+        let parent = ClassBuilder::<P>::default();
+
+        let to_parent = Upcaster::new(upcast, upcast_mut);
+        self.ancestors = parent.ancestors.iter().map(|(id, ancestor)| {
+            (*id, ancestor.after(to_parent.clone()))
         }).collect();
+        self.ancestors.insert(TypeId::of::<P>(), to_parent);
 
-        let name = env.string(C::PINAR_CLASS_ID)?;
-        props.push( napi_property_descriptor {
-            utf8name: std::ptr::null_mut(),
-            name: name.get_value().value,
-            method: Some(__pinar_nop),
-            getter: None,
-            setter: None,
-            value: std::ptr::null_mut(),
-            attributes: napi_property_attributes::napi_default,
-            data: std::ptr::null_mut(),
+        let wrap = |handler: Option<&Rc<dyn ClassMethodHandler<P>>>| {
+            handler.map(|handler| {
+                Rc::new(Upcast { handler: Rc::clone(handler), upcast, upcast_mut }) as Rc<dyn ClassMethodHandler<C>>
+            })
+        };
+
+        self.inherited = parent.properties().into_iter().map(|prop| {
+            ClassProperty {
+                name: prop.name.clone(),
+                method: wrap(prop.method.as_ref()),
+                getter: wrap(prop.getter.as_ref()),
+                setter: wrap(prop.setter.as_ref()),
+                value: prop.value.clone(),
+                or_this: prop.or_this,
+            }
+        }).collect();
+        self.parent = Some(ClassBuilder::<P>::create_value);
+        self
+    }
+
+    /// Returns the own and inherited properties of the class
+    fn properties(&self) -> Vec<&ClassProperty<C>> {
+        let inherited = self.inherited.iter().filter(|prop| {
+            self.props.iter().all(|p| p.name != prop.name)
+        });
+        self.props.iter().chain(inherited).collect()
+    }
+
+    /// Returns the property with this name, creating it if needed
+    fn property<S: AsRef<str>>(&mut self, name: S, kind: PropertyKind) -> &mut ClassProperty<C> {
+        let name = CString::new(name.as_ref()).unwrap();
+        self.property_key(PropertyKey::Name(name), kind)
+    }
+
+    /// Returns the property with this key, creating it if needed
+    ///
+    /// Panics if the property is already defined with another kind.
+    fn property_key(&mut self, key: PropertyKey, kind: PropertyKind) -> &mut ClassProperty<C> {
+        match self.props.iter().position(|p| p.name == key) {
+            Some(index) => {
+                let prop = &mut self.props[index];
+                if let Some(defined) = prop.kind().filter(|defined| *defined != kind) {
+                    panic!(
+                        "pinar: property {} of class {} is already defined as {:?}, it can't be redefined as {:?}",
+                        key, self.name, defined, kind
+                    );
+                }
+                prop
+            }
+            None => {
+                self.props.push(ClassProperty::new(key));
+                self.props.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// Add a static method to the class
+    ///
+    /// The method is defined on the constructor, it doesn't receive any instance:
+    ///
+    /// ```
+    /// fn default_properties(builder: ClassBuilder<Self>) -> ClassBuilder<Self> {
+    ///     builder.with_static_method("fromJson", |json: String| -> JsResult<AsJsClass<Self>> {
+    ///         Ok(AsJsClass(serde_json::from_str(&json)?))
+    ///     })
+    /// }
+    ///
+    /// // From javascript:
+    /// // const instance = MyStruct.fromJson('{ "num": 3 }');
+    /// ```
+    pub fn with_static_method<S, F, A, R>(mut self, name: S, fun: F) -> Self
+    where
+        S: AsRef<str>,
+        A: FromArguments + 'static,
+        R: for <'env> JsReturn<'env> + 'static,
+        F: CallbackFn<A, R> + 'static
+    {
+        let name = name.as_ref();
+        let fun = Rc::new(ModuleFunction::new(name, fun));
+        let fun_name = name.to_owned();
+
+        self.statics.push(StaticProperty {
+            name: CString::new(name).unwrap(),
+            value: Rc::new(move |env| {
+                env.function_internal(&fun_name, Rc::clone(&fun))
+                   .map(|f| f.get_value())
+            })
         });
+        self
+    }
 
-        let data: Vec<_> = self.props.iter().map(|prop| {
-            prop.method.as_ref().map(|p| Rc::clone(p)).unwrap_or_else(|| {
-                prop.accessor.as_ref().map(|p| Rc::clone(&p)).unwrap()
+    /// Add a static value to the class
+    ///
+    /// The value is converted to JS each time the class is created.
+    pub fn with_static_value<S, V>(mut self, name: S, value: V) -> Self
+    where
+        S: AsRef<str>,
+        V: for <'env> ToJs<'env> + 'static
+    {
+        self.statics.push(StaticProperty {
+            name: CString::new(name.as_ref()).unwrap(),
+            value: Rc::new(move |env| value.to_js(env).map(|v| v.get_value()))
+        });
+        self
+    }
+
+    /// Build the class with its properties
+    fn create_internal<'e>(&self, env: &Env) -> JsResult<(JsFunction<'e>, Rc<JsClassData<C>>)> {
+        let properties = self.properties();
+
+        let mut props = properties.iter().enumerate().map(|(index, prop)| {
+            let (utf8name, name) = match (&prop.name, prop.name.symbol(env)?) {
+                (PropertyKey::Name(name), _) => (name.as_ptr(), std::ptr::null_mut()),
+                (_, symbol) => (std::ptr::null(), symbol.map(|s| s.value).unwrap_or_else(std::ptr::null_mut))
+            };
+
+            let method = match (&prop.method, prop.or_this) {
+                (Some(_), true) => Some(C::__pinar_class_dispatch_or_this as _),
+                (Some(_), false) => Some(C::__pinar_class_dispatch as _),
+                _ => None
+            };
+            let value = match &prop.value {
+                Some(value) => value(*env)?.value,
+                _ => std::ptr::null_mut()
+            };
+
+            Ok(RawPropertyDescriptor {
+                utf8name,
+                name,
+                method,
+                getter: if prop.getter.is_some() { Some(C::__pinar_class_dispatch) } else { None },
+                setter: if prop.setter.is_some() { Some(C::__pinar_class_dispatch_setter) } else { None },
+                value,
+                attributes: napi_property_attributes::napi_default as u32,
+                data: index as *mut std::ffi::c_void,
             })
+        }).collect::<JsResult<Vec<_>>>()?;
+
+        // Like static members of JS classes, they can be reassigned and deleted
+        let static_attributes = napi_property_attributes::napi_static as u32
+            | napi_property_attributes::napi_writable as u32
+            | napi_property_attributes::napi_configurable as u32;
+
+        for prop in &self.statics {
+            props.push(RawPropertyDescriptor {
+                utf8name: prop.name.as_ptr(),
+                name: std::ptr::null_mut(),
+                method: None,
+                getter: None,
+                setter: None,
+                value: (prop.value)(*env)?.value,
+                attributes: static_attributes,
+                data: std::ptr::null_mut(),
+            });
+        }
+
+        let methods = properties.iter().map(|prop| {
+            prop.method.as_ref().or(prop.getter.as_ref()).cloned()
         }).collect();
+        let setters = properties.iter().map(|prop| prop.setter.clone()).collect();
 
-        let data_ptr = Rc::into_raw(Rc::new(JsClassData {
-            id: TypeId::of::<C>(),
-            methods: data,
-            args_rust,
-            instance
-        }));
+        let class_data = Rc::new(JsClassData {
+            methods,
+            setters,
+            pending: RefCell::new(None)
+        });
+        let data_ptr = Rc::into_raw(Rc::clone(&class_data));
 
         let mut result = Value::new(*env);
 
@@ -380,7 +957,7 @@ impl<C: JsClass + 'static> ClassBuilder<C> {
             Some(C::__pinar_class_constructor),
             data_ptr as *mut c_void,
             props.len(),
-            props.as_ptr(),
+            props.as_ptr() as *const napi_property_descriptor,
             result.get_mut()
         ))?;
 
@@ -393,25 +970,127 @@ impl<C: JsClass + 'static> ClassBuilder<C> {
             std::ptr::null_mut()
         ))?;
 
-        Ok(JsFunction::from(result))
+        if let Some(parent) = self.parent {
+            Self::set_prototype(env, result, parent(*env)?)?;
+        }
+        self.register_descendant();
+
+        Ok((JsFunction::from(result), class_data))
+    }
+
+    /// Makes the instances of `C` castable to its ancestors
+    fn register_descendant(&self) {
+        DESCENDANTS.with(|descendants| {
+            let mut descendants = descendants.borrow_mut();
+            for (ancestor, upcast) in &self.ancestors {
+                let classes = descendants.entry(*ancestor).or_default();
+                if classes.iter().all(|d| d.class != TypeId::of::<C>()) {
+                    classes.push(Descendant::new::<C>(upcast.clone()));
+                }
+            }
+        });
+    }
+
+    /// Makes the class `child` extends `parent`
+    fn set_prototype(env: &Env, child: Value, parent: Value) -> JsResult<()> {
+        let set_prototype = env.global()?
+                               .get("Object")?
+                               .as_jsobject()?
+                               .get("setPrototypeOf")?
+                               .as_jsfunction()?;
+
+        let child = JsObject::from(child);
+        let parent = JsObject::from(parent);
+
+        set_prototype.call((
+            child.get("prototype")?.get_value(),
+            parent.get("prototype")?.get_value()
+        ))?;
+        set_prototype.call((child.get_value(), parent.get_value()))?;
+        Ok(())
+    }
+
+    fn create_value(env: Env) -> JsResult<Value> {
+        Self::constructor(&env).map(|class| class.get_value())
     }
 
     pub fn create<'e>(&self, env: &Env) -> JsResult<JsFunction<'e>> {
-        self.create_internal(env, None, None)
+        self.create_internal(env).map(|(class, _)| class)
+    }
+
+    /// Returns the class constructor of the env.
+    ///
+    /// The class is created on the first call, the next calls return the
+    /// same constructor.
+    pub(crate) fn constructor<'e>(env: &Env) -> JsResult<JsFunction<'e>> {
+        Self::cached(env).map(|(class, _)| class)
+    }
+
+    fn cached<'e>(env: &Env) -> JsResult<(JsFunction<'e>, Rc<JsClassData<C>>)> {
+        let key = (env.env() as usize, TypeId::of::<C>());
+        let cached = CLASSES.with(|classes| {
+            classes.borrow().get(&key).map(|c| (c.constructor, Rc::clone(&c.data)))
+        });
+
+        if let Some((constructor, data)) = cached {
+            let mut result = Value::new(*env);
+            napi_call!(napi_get_reference_value(
+                env.env(),
+                constructor,
+                result.get_mut()
+            ))?;
+            let data = data.downcast::<JsClassData<C>>().map_err(|_| JsClassError::WrongClass)?;
+            return Ok((JsFunction::from(result), data));
+        }
+
+        let (class, data) = ClassBuilder::<C>::default().create_internal(env)?;
+
+        let mut constructor: napi_ref = std::ptr::null_mut();
+        napi_call!(napi_create_reference(
+            env.env(),
+            class.get_value().value,
+            1,
+            &mut constructor as *mut napi_ref
+        ))?;
+
+        let first_of_env = CLASSES.with(|classes| {
+            let mut classes = classes.borrow_mut();
+            let first = classes.keys().all(|(class_env, _)| *class_env != key.0);
+            classes.insert(key, CachedClass { constructor, data: Rc::clone(&data) as Rc<dyn Any> });
+            first
+        });
+
+        if first_of_env {
+            napi_call!(napi_add_env_cleanup_hook(
+                env.env(),
+                Some(__pinar_clear_classes),
+                env.env() as *mut c_void
+            ))?;
+        }
+
+        Ok((class, data))
+    }
+
+    /// Call the constructor of the class with `pending` instead of JS arguments
+    fn construct<'e>(env: Env, pending: Pending<C>) -> JsResult<JsObject<'e>> {
+        let (class, data) = Self::cached(&env)?;
+
+        data.pending.replace(Some(pending));
+        let result = class.new_instance(());
+        // Not consumed if the constructor hasn't been called
+        data.pending.replace(None);
+
+        result
     }
 
     /// Instantiate the JS class from Rust
     pub fn new_instance<'e>(env: Env, args: C::ArgsConstructor) -> JsResult<JsObject<'e>> {
-        let builder = ClassBuilder::<C>::default();
-        let fun = builder.create_internal(&env, Some(args), None)?;
-        fun.new_instance(())
+        Self::construct(env, Pending::Args(args))
     }
 
     /// Instantiate the Js class from Rust with its Rust instance
     pub fn from_instance<'e>(env: Env, instance: C) -> JsResult<JsObject<'e>> {
-        let builder = ClassBuilder::<C>::default();
-        let fun = builder.create_internal(&env, None, Some(instance))?;
-        fun.new_instance(())
+        Self::construct(env, Pending::Instance(instance))
     }
 }
 
@@ -422,8 +1101,18 @@ impl<C: JsClass + 'static> ClassBuilder<C> {
 // TODO: Use https://github.com/rust-lang/rust/pull/55986
 //       when it reaches stable
 
+/// Marker of the methods taking `&self`
+#[doc(hidden)]
+pub struct SharedMethod;
+
+/// Marker of the methods taking `&mut self`
+#[doc(hidden)]
+pub struct MutMethod;
+
 /// Trait to implement for a method of a class
-pub trait MethodFn<C, A, R>
+///
+/// `K` is [`SharedMethod`] or [`MutMethod`], depending on the receiver.
+pub trait MethodFn<C, A, R, K>
 where
     C: JsClass,
     A: FromArguments,
@@ -437,7 +1126,7 @@ macro_rules! impl_methodfn {
         $( ( $($arg:ident),* ) ),*
     ) => {
         $(
-            impl<$($arg,)* R, Class, Fun> MethodFn<Class, ($($arg,)*), R> for Fun
+            impl<$($arg,)* R, Class, Fun> MethodFn<Class, ($($arg,)*), R, MutMethod> for Fun
             where
                 Fun: Fn(&mut Class, $($arg,)*) -> R + 'static,
                 Class: JsClass,
@@ -449,6 +1138,19 @@ macro_rules! impl_methodfn {
                     ClassMethod::new(move |s, ($($arg,)*)| (self)(s, $($arg,)*))
                 }
             }
+
+            impl<$($arg,)* R, Class, Fun> MethodFn<Class, ($($arg,)*), R, SharedMethod> for Fun
+            where
+                Fun: Fn(&Class, $($arg,)*) -> R + 'static,
+                Class: JsClass,
+                $($arg : FromArguments + 'static,)*
+                R: for<'env> JsReturn<'env> + 'static
+            {
+                #[allow(non_snake_case)]
+                fn make(self) -> ClassMethod<Class, ($($arg,)*), R> {
+                    ClassMethod::new_shared(move |s, ($($arg,)*)| (self)(s, $($arg,)*))
+                }
+            }
         )*
     }
 }
@@ -479,7 +1181,15 @@ where
     A: FromArguments,
     R: for <'env> JsReturn<'env>
 {
-    fun: Box<dyn Fn(&mut C, A) -> R>,
+    fun: MethodKind<C, A, R>,
+}
+
+type SharedFn<C, A, R> = Box<dyn Fn(&C, A) -> R>;
+type MutFn<C, A, R> = Box<dyn Fn(&mut C, A) -> R>;
+
+enum MethodKind<C, A, R> {
+    Shared(SharedFn<C, A, R>),
+    Mut(MutFn<C, A, R>),
 }
 
 impl<C, A, R> ClassMethod<C, A, R>
@@ -493,14 +1203,64 @@ where
         F: Fn(&mut C, A) -> R + 'static
     {
         ClassMethod {
-            fun: Box::new(fun),
+            fun: MethodKind::Mut(Box::new(fun)),
+        }
+    }
+
+    fn new_shared<F>(fun: F) -> Self
+    where
+        F: Fn(&C, A) -> R + 'static
+    {
+        ClassMethod {
+            fun: MethodKind::Shared(Box::new(fun)),
         }
     }
 }
 
 /// Trait to call a method of the class
 trait ClassMethodHandler<C: JsClass> {
-    fn call(&self, this: &mut C, args: &Arguments) -> JsResult<Option<Value>>;
+    /// Returns true if the method only needs `&C`
+    fn is_shared(&self) -> bool;
+
+    /// Call a shared method
+    fn call_shared(&self, this: &C, args: &Arguments) -> JsResult<Option<Value>>;
+
+    /// Call a method, shared or not
+    fn call_mut(&self, this: &mut C, args: &Arguments) -> JsResult<Option<Value>>;
+
+    /// Borrow the instance and call the method
+    fn call(&self, this: &RefCell<C>, args: &Arguments) -> JsResult<Option<Value>> {
+        let borrowed = JsClassError::Borrowed(C::CLASSNAME);
+
+        if self.is_shared() {
+            let this = this.try_borrow().map_err(|_| borrowed)?;
+            self.call_shared(&this, args)
+        } else {
+            let mut this = this.try_borrow_mut().map_err(|_| borrowed)?;
+            self.call_mut(&mut this, args)
+        }
+    }
+}
+
+/// Method of a parent class, called on its child
+struct Upcast<C: JsClass, P: JsClass> {
+    handler: Rc<dyn ClassMethodHandler<P>>,
+    upcast: fn(&C) -> &P,
+    upcast_mut: fn(&mut C) -> &mut P
+}
+
+impl<C: JsClass, P: JsClass> ClassMethodHandler<C> for Upcast<C, P> {
+    fn is_shared(&self) -> bool {
+        self.handler.is_shared()
+    }
+
+    fn call_shared(&self, this: &C, args: &Arguments) -> JsResult<Option<Value>> {
+        self.handler.call_shared((self.upcast)(this), args)
+    }
+
+    fn call_mut(&self, this: &mut C, args: &Arguments) -> JsResult<Option<Value>> {
+        self.handler.call_mut((self.upcast_mut)(this), args)
+    }
 }
 
 impl<C, A, R> ClassMethodHandler<C> for ClassMethod<C, A, R>
@@ -509,11 +1269,26 @@ where
     A: FromArguments,
     R: for <'env> JsReturn<'env>
 {
-    fn call(&self, this: &mut C, args: &Arguments) -> JsResult<Option<Value>> {
+    fn is_shared(&self) -> bool {
+        matches!(self.fun, MethodKind::Shared(_))
+    }
+
+    fn call_shared(&self, this: &C, args: &Arguments) -> JsResult<Option<Value>> {
+        let env = args.env();
+
+        match &self.fun {
+            MethodKind::Shared(fun) => fun(this, A::from_args(args)?).get_result(env),
+            MethodKind::Mut(_) => Err(JsClassError::WrongHandler.into())
+        }
+    }
+
+    fn call_mut(&self, this: &mut C, args: &Arguments) -> JsResult<Option<Value>> {
         let env = args.env();
-        let args = A::from_args(args)?;
 
-        (self.fun)(this, args).get_result(env)
+        match &self.fun {
+            MethodKind::Shared(fun) => fun(this, A::from_args(args)?).get_result(env),
+            MethodKind::Mut(fun) => fun(this, A::from_args(args)?).get_result(env)
+        }
     }
 }
 
@@ -559,6 +1334,230 @@ where
     }
 }
 
+/// A reference to an instance of the class `C`, received as argument.
+///
+/// It keeps the Javascript object alive, it can be stored and used after
+/// the function returns.
+/// To borrow the instance for the duration of the call, see [`ClassBorrow`]
+/// and [`ClassBorrowMut`].
+///
+/// Receiving an object which is not an instance of `C`, nor of a class
+/// inheriting from `C` (see [`ClassBuilder::with_parent`]), throws a `TypeError`.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// impl Player {
+///     fn follow(&mut self, other: ClassRef<Player>) {
+///         self.leader = Some(other);
+///     }
+/// }
+/// ```
+///
+/// [`ClassBorrow`]: ./struct.ClassBorrow.html
+/// [`ClassBorrowMut`]: ./struct.ClassBorrowMut.html
+/// [`ClassBuilder::with_parent`]: ./struct.ClassBuilder.html#method.with_parent
+pub struct ClassRef<C: JsClass> {
+    object: Rc<JsRef<JsObject<'static>>>,
+    value: *const RefCell<AnyInstance>,
+    /// Set when the instance is of a class inheriting from `C`
+    upcast: Option<Upcaster>,
+    class: PhantomData<C>
+}
+
+impl<C: JsClass> ClassRef<C> {
+    /// Returns the Javascript object of the instance
+    pub fn object<'e>(&self) -> JsResult<JsObject<'e>> {
+        JsRef::<JsObject<'static>>::deref(&self.object)
+    }
+
+    /// Borrows the instance.
+    ///
+    /// Fails if it is mutably borrowed, by a running method for example.
+    pub fn borrow(&self) -> JsResult<Ref<'_, C>> {
+        let value = self.cell().try_borrow().map_err(|_| JsClassError::Borrowed(C::CLASSNAME))?;
+        Ok(Ref::map(value, |value| {
+            let value = match &self.upcast {
+                Some(upcast) => (upcast.shared)(value),
+                _ => value
+            };
+            // Checked by `unwrap_class`
+            value.downcast_ref::<C>().unwrap()
+        }))
+    }
+
+    /// Mutably borrows the instance.
+    ///
+    /// Fails if it is already borrowed, by a running method for example.
+    pub fn borrow_mut(&self) -> JsResult<RefMut<'_, C>> {
+        let value = self.cell().try_borrow_mut().map_err(|_| JsClassError::Borrowed(C::CLASSNAME))?;
+        Ok(RefMut::map(value, |value| {
+            let value = match &self.upcast {
+                Some(upcast) => (upcast.mutable)(value),
+                _ => value
+            };
+            // Checked by `unwrap_class`
+            value.downcast_mut::<C>().unwrap()
+        }))
+    }
+
+    fn cell(&self) -> &RefCell<AnyInstance> {
+        // The instance lives as long as the JS object, kept alive by our reference
+        unsafe { &*self.value }
+    }
+
+    fn is_instance(any: &JsAny) -> bool {
+        match any {
+            JsAny::Object(object) => unwrap_class::<C>(object).is_some(),
+            _ => false
+        }
+    }
+}
+
+impl<C: JsClass> Clone for ClassRef<C> {
+    fn clone(&self) -> Self {
+        ClassRef {
+            object: Rc::clone(&self.object),
+            value: self.value,
+            upcast: self.upcast.clone(),
+            class: PhantomData
+        }
+    }
+}
+
+impl<C: JsClass> FromArguments for ClassRef<C> {
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        let not_instance = |got| ArgumentsError::NotInstance(C::CLASSNAME, got, args.arg_number());
+
+        let object = match args.next_arg() {
+            Some(JsAny::Object(object)) => object,
+            Some(other) => return Err(not_instance(other.type_name()).into()),
+            _ => return Err(ArgumentsError::missing(args.arg_number()))
+        };
+
+        match unwrap_class::<C>(&object) {
+            Some((value, upcast)) => Ok(ClassRef {
+                object: Rc::new(object.as_js_ref()?),
+                value,
+                upcast,
+                class: PhantomData
+            }),
+            _ => Err(not_instance("object").into())
+        }
+    }
+
+    fn signature(signature: &mut Signature) {
+        signature.push(ArgSignature::new(C::CLASSNAME, Self::is_instance));
+    }
+}
+
+/// A borrowed instance of the class `C`, received as argument.
+///
+/// The instance is borrowed until the `ClassBorrow` is dropped, usually at
+/// the end of the call: a method of the instance called meanwhile with
+/// `&mut self` throws an error.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// impl Player {
+///     fn is_ally(&self, other: ClassBorrow<Player>) -> bool {
+///         self.team == other.team
+///     }
+/// }
+/// ```
+pub struct ClassBorrow<C: JsClass> {
+    // Declared first, to be dropped before the reference to the JS object
+    guard: Ref<'static, C>,
+    _class: ClassRef<C>
+}
+
+/// A mutably borrowed instance of the class `C`, received as argument.
+///
+/// The instance is borrowed until the `ClassBorrowMut` is dropped, usually
+/// at the end of the call: a method of the instance called meanwhile throws
+/// an error.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// impl Player {
+///     fn attack(&self, mut other: ClassBorrowMut<Player>) {
+///         other.life -= self.strength;
+///     }
+/// }
+/// ```
+pub struct ClassBorrowMut<C: JsClass> {
+    // Declared first, to be dropped before the reference to the JS object
+    guard: RefMut<'static, C>,
+    _class: ClassRef<C>
+}
+
+impl<C: JsClass> ClassBorrow<C> {
+    /// Borrows the instance of `class`
+    pub fn new(class: ClassRef<C>) -> JsResult<ClassBorrow<C>> {
+        let guard = class.borrow()?;
+        // The guard is dropped before `class`, which keeps the instance alive,
+        // and it is only reachable through `&self`
+        let guard = unsafe { std::mem::transmute::<Ref<'_, C>, Ref<'static, C>>(guard) };
+        Ok(ClassBorrow { guard, _class: class })
+    }
+}
+
+impl<C: JsClass> ClassBorrowMut<C> {
+    /// Mutably borrows the instance of `class`
+    pub fn new(class: ClassRef<C>) -> JsResult<ClassBorrowMut<C>> {
+        let guard = class.borrow_mut()?;
+        // The guard is dropped before `class`, which keeps the instance alive,
+        // and it is only reachable through `&self` and `&mut self`
+        let guard = unsafe { std::mem::transmute::<RefMut<'_, C>, RefMut<'static, C>>(guard) };
+        Ok(ClassBorrowMut { guard, _class: class })
+    }
+}
+
+impl<C: JsClass> Deref for ClassBorrow<C> {
+    type Target = C;
+    fn deref(&self) -> &C {
+        &self.guard
+    }
+}
+
+impl<C: JsClass> Deref for ClassBorrowMut<C> {
+    type Target = C;
+    fn deref(&self) -> &C {
+        &self.guard
+    }
+}
+
+impl<C: JsClass> DerefMut for ClassBorrowMut<C> {
+    fn deref_mut(&mut self) -> &mut C {
+        &mut self.guard
+    }
+}
+
+impl<C: JsClass> FromArguments for ClassBorrow<C> {
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        ClassBorrow::new(ClassRef::from_args(args)?)
+    }
+
+    fn signature(signature: &mut Signature) {
+        ClassRef::<C>::signature(signature)
+    }
+}
+
+impl<C: JsClass> FromArguments for ClassBorrowMut<C> {
+    fn from_args(args: &Arguments) -> JsResult<Self> {
+        ClassBorrowMut::new(ClassRef::from_args(args)?)
+    }
+
+    fn signature(signature: &mut Signature) {
+        ClassRef::<C>::signature(signature)
+    }
+}
+
 // impl JsClass for SomeClass {
 //     const CLASSNAME: &'static str = "RustClass";
 //     type ArgsConstructor = (String, i64);
@@ -0,0 +1,105 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use crate::error::JsFunctionError;
+use crate::module::ModuleFunction;
+use crate::prelude::*;
+
+/// A Rust closure callable from Javascript.
+///
+/// Unlike [`Env::function`], which immediately creates a single Javascript
+/// function from a `Fn`:
+/// - a `JsClosure` accepts `FnMut` and `FnOnce` closures
+/// - it doesn't need an `Env`, it implements [`ToJs`]: it can be returned or
+///   passed as argument, each conversion creates a Javascript function
+///   sharing the same Rust closure
+/// - the captured state is dropped once the `JsClosure` and all the
+///   Javascript functions created from it are garbage collected
+///
+/// The arguments of the closure are received the same way as exported
+/// functions: a single type implementing [`FromArguments`], use a tuple
+/// for multiple arguments.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn on_data(env: Env, emitter: JsObject) -> JsResult<JsClosure> {
+///     let counter = Rc::new(Cell::new(0));
+///     let counter2 = Rc::clone(&counter);
+///
+///     let handler = JsClosure::new(move |data: String| {
+///         counter2.set(counter2.get() + 1);
+///     });
+///     emitter.get("on")?.as_jsfunction()?.call_with_this(emitter, ("data", handler))?;
+///
+///     // The disposer returned to javascript
+///     Ok(JsClosure::once(move |()| {
+///         counter.get()
+///     }))
+/// }
+/// ```
+///
+/// [`Env::function`]: ./struct.Env.html#method.function
+/// [`FromArguments`]: ./trait.FromArguments.html
+/// [`ToJs`]: ./trait.ToJs.html
+pub struct JsClosure {
+    function: Rc<ModuleFunction>
+}
+
+impl JsClosure {
+    /// Creates a `JsClosure` from a `Fn` closure
+    pub fn new<F, A, R>(fun: F) -> JsClosure
+    where
+        A: FromArguments + 'static,
+        R: for<'env> JsReturn<'env> + 'static,
+        F: Fn(A) -> R + 'static
+    {
+        JsClosure {
+            function: Rc::new(ModuleFunction::new("", fun))
+        }
+    }
+
+    /// Creates a `JsClosure` from a `FnMut` closure.
+    ///
+    /// Calling the closure recursively (from a Javascript function it calls)
+    /// throws an error.
+    pub fn new_mut<F, A, R>(fun: F) -> JsClosure
+    where
+        A: FromArguments + 'static,
+        R: for<'env> JsReturn<'env> + 'static,
+        F: FnMut(A) -> R + 'static
+    {
+        let fun = RefCell::new(fun);
+
+        JsClosure::new(move |args: A| -> JsResult<R> {
+            let mut fun = fun.try_borrow_mut()
+                             .map_err(|_| JsFunctionError::ClosureRunning)?;
+            Ok((*fun)(args))
+        })
+    }
+
+    /// Creates a `JsClosure` from a `FnOnce` closure.
+    ///
+    /// Calling the closure more than once throws an error.
+    pub fn once<F, A, R>(fun: F) -> JsClosure
+    where
+        A: FromArguments + 'static,
+        R: for<'env> JsReturn<'env> + 'static,
+        F: FnOnce(A) -> R + 'static
+    {
+        let fun = Cell::new(Some(fun));
+
+        JsClosure::new(move |args: A| -> JsResult<R> {
+            let fun = fun.take().ok_or(JsFunctionError::ClosureCalled)?;
+            Ok(fun(args))
+        })
+    }
+}
+
+impl<'e> ToJs<'e> for JsClosure {
+    type Value = JsFunction<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsFunction<'e>> {
+        env.function_internal("", Rc::clone(&self.function))
+    }
+}
@@ -9,6 +9,8 @@ use crate::jsreturn::JsReturn;
 use crate::arguments::{FromArguments, Arguments};
 use std::sync::Arc;
 use crate::external::External;
+#[cfg(feature = "napi8")]
+use crate::type_tag::{TypeTag, tag_value};
 use std::rc::Rc;
 use crate::classes::__pinar_drop_box;
 use std::ffi::c_void;
@@ -270,6 +272,19 @@ impl Env {
         Ok(JsObject::from(global))
     }
 
+    /// Returns the well-known symbol `Symbol[name]` (ex: `Symbol.iterator`)
+    pub(crate) fn well_known_symbol(&self, name: &str) -> JsResult<Value> {
+        let symbol = JsObject::from(self.global()?.get("Symbol")?.get_value());
+        Ok(symbol.get(name)?.get_value())
+    }
+
+    /// Returns the symbol `Symbol.for(name)` of the global registry
+    pub(crate) fn registered_symbol(&self, name: &str) -> JsResult<Value> {
+        let symbol = JsObject::from(self.global()?.get("Symbol")?.get_value());
+        let symbol_for = symbol.get("for")?.as_jsfunction()?;
+        Ok(symbol_for.call(name)?.get_value())
+    }
+
     /// Returns the javascript `undefined`.
     pub fn undefined<'e>(&self) -> JsResult<JsUndefined<'e>> {
         let mut undefined = Value::new(*self);
@@ -315,6 +330,8 @@ impl Env {
             std::ptr::null_mut(),
             result.get_mut()
         ))?;
+        #[cfg(feature = "napi8")]
+        tag_value(result, TypeTag::of::<External<T>>())?;
 
         Ok(JsExternal::from(result))
     }
@@ -340,6 +357,8 @@ impl Env {
             std::ptr::null_mut(),
             result.get_mut()
         ))?;
+        #[cfg(feature = "napi8")]
+        tag_value(result, TypeTag::of::<External<T>>())?;
 
         Ok(JsExternal::from(result))
     }
@@ -365,6 +384,8 @@ impl Env {
             std::ptr::null_mut(),
             result.get_mut()
         ))?;
+        #[cfg(feature = "napi8")]
+        tag_value(result, TypeTag::of::<External<T>>())?;
 
         Ok(JsExternal::from(result))
     }
@@ -424,8 +445,20 @@ impl Env {
     }
 
     pub(crate) fn callback_info<D>(&self, info: napi_callback_info) -> JsResult<(*mut D, Arguments)> {
-        let mut argc: usize = 12;
-        let mut argv: Vec<napi_value> = Vec::with_capacity(argc);
+        // Reads the number of arguments first, napi writes back the real
+        // argc which can be greater than the capacity of `argv`
+        let mut argc: usize = 0;
+        napi_call!(napi_get_cb_info(
+            self.env,
+            info,
+            &mut argc as *mut usize,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+            std::ptr::null_mut()
+        ))?;
+
+        let capacity = argc;
+        let mut argv: Vec<napi_value> = Vec::with_capacity(capacity);
         let mut this = Value::new(*self);
         let mut data_ptr: *mut D = std::ptr::null_mut();
 
@@ -438,11 +471,29 @@ impl Env {
             &mut data_ptr as *mut *mut D as *mut *mut std::ffi::c_void
         ))?;
 
-        unsafe { argv.set_len(argc) };
+        unsafe { argv.set_len(argc.min(capacity)) };
 
         Ok((data_ptr, Arguments::new(*self, this, &argv)?))
     }
 
+    /// Returns `new.target` of the call, or `None` when the function
+    /// has not been called as a constructor.
+    pub(crate) fn new_target(&self, info: napi_callback_info) -> JsResult<Option<Value>> {
+        let mut result = Value::new(*self);
+
+        napi_call!(napi_get_new_target(
+            self.env,
+            info,
+            result.get_mut()
+        ))?;
+
+        if result.get().is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(result))
+        }
+    }
+
     /// Throws the JavaScript value provided
     ///
     /// # Example
@@ -554,6 +605,80 @@ impl Env {
         Err(Status::PendingException.into())
     }
 
+    /// Converts any Rust value implementing `Serialize` to Javascript.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Serialize)]
+    /// struct Point {
+    ///     x: f64,
+    ///     y: f64
+    /// }
+    ///
+    /// #[pinar]
+    /// fn my_func(env: Env) -> JsResult<JsAny> {
+    ///     env.serialize(&Point { x: 1.0, y: 2.0 })
+    /// }
+    /// ```
+    #[cfg(feature = "pinar-serde")]
+    pub fn serialize<'e, T>(&self, value: &T) -> JsResult<JsAny<'e>>
+    where
+        T: serde::Serialize + ?Sized
+    {
+        let value = crate::pinar_serde::ser::serialize_to_js(*self, value)?;
+        JsAny::from(value)
+    }
+
+    /// Converts any Rust value implementing `Serialize` to Javascript,
+    /// with the configuration `config`.
+    ///
+    /// See [`SerializerConfig`].
+    ///
+    /// [`SerializerConfig`]: ./struct.SerializerConfig.html
+    #[cfg(feature = "pinar-serde")]
+    pub fn serialize_with<'e, T>(&self, value: &T, config: &crate::pinar_serde::ser::SerializerConfig) -> JsResult<JsAny<'e>>
+    where
+        T: serde::Serialize + ?Sized
+    {
+        let value = crate::pinar_serde::ser::serialize_to_js_with(*self, value, config)?;
+        JsAny::from(value)
+    }
+
+    /// Creates a Javascript error without throwing it
+    pub(crate) fn create_error(&self, msg: &str, code: Option<&str>, kind: crate::JsErrorKind) -> JsResult<Value> {
+        use crate::JsErrorKind;
+
+        let msg = self.string(msg)?.get_value();
+        let code = match code {
+            Some(code) => self.string(code)?.get_value().get(),
+            _ => std::ptr::null_mut()
+        };
+        let mut result = Value::new(*self);
+
+        match kind {
+            JsErrorKind::Error => napi_call!(napi_create_error(self.env, code, msg.get(), result.get_mut())),
+            JsErrorKind::TypeError => napi_call!(napi_create_type_error(self.env, code, msg.get(), result.get_mut())),
+            JsErrorKind::RangeError => napi_call!(napi_create_range_error(self.env, code, msg.get(), result.get_mut())),
+        }?;
+
+        Ok(result)
+    }
+
+    /// Converts a Rust error to a Javascript error, without throwing it.
+    ///
+    /// A pending Javascript exception is cleared and returned as is.
+    pub(crate) fn error_to_js(&self, error: &crate::Error) -> JsResult<Value> {
+        if let Some(Status::PendingException) = error.downcast_ref::<Status>() {
+            let mut exception = Value::new(*self);
+            napi_call!(napi_get_and_clear_last_exception(self.env, exception.get_mut()))?;
+            return Ok(exception);
+        }
+
+        let error = error.as_js_error();
+        self.create_error(&error.get_msg(), error.get_code().as_deref(), error.get_kind())
+    }
+
     /// Executes the provided script.
     ///
     /// # Example
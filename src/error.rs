@@ -1,4 +1,5 @@
 use crate::status::Status;
+use crate::objects::JsAny;
 
 use backtrace::Backtrace;
 
@@ -48,13 +49,15 @@ impl Error {
 pub(crate) struct JsReturnRefError;
 
 #[derive(Display, Debug)]
-#[display(fmt = "Null pointer on external data")]
-pub(crate) struct JsExternalError;
+pub(crate) enum JsExternalError {
+    #[display(fmt = "Null pointer on external data")]
+    NullPointer,
+    #[display(fmt = "The external value doesn't contain a {}", _0)]
+    WrongType(&'static str),
+}
 
 #[derive(Display, Debug)]
 pub(crate) enum JsClassError {
-    #[display(fmt = "Fail to extract external value from class. Please report on pinar repo.")]
-    ExternalClassData,
     #[display(fmt = "Wrong handler class. Please report on pinar repo.")]
     WrongHandler,
     #[display(fmt = "A class method has been called with the wrong class. Check your JS !")]
@@ -65,18 +68,22 @@ pub(crate) enum JsClassError {
     ThisMethod(&'static str),
     #[display(fmt = "Constructor of the class {} is not defined", _0)]
     NoConstructor(&'static str),
-    #[display(fmt = "Fail to unwrap the class. Please report on pinar repo.")]
-    Unwrap,
+    #[display(fmt = "The instance of {} is already in use by a method, it can't be borrowed again", _0)]
+    Borrowed(&'static str),
 }
 
 #[derive(Display, Debug)]
 pub(crate) enum JsFunctionError {
-    #[display(fmt = "Multiple overload of the function {} failed.", _0)]
-    ArgumentsOverload(String),
+    #[display(fmt = "No overload of the function {} matches the arguments:\n{}", _0, _1)]
+    ArgumentsOverload(String, String),
 //    #[display(fmt = "{{ function {} }}: {}.", _0, _1)]
 //    Arguments(String, String),
     #[display(fmt = "Fail to dispatch the function, please report on pinar repo.")]
     WrongFunctionData,
+    #[display(fmt = "The closure is already running, it can't be called recursively.")]
+    ClosureRunning,
+    #[display(fmt = "The closure has already been called, it can be called only once.")]
+    ClosureCalled,
 }
 
 #[derive(Display, Debug, Clone)]
@@ -85,14 +92,42 @@ pub enum ArgumentsError {
     Missing(usize),
     #[display(fmt = "Wrong type, expected a {} on the {}th argument", _0, _1)]
     WrongType(String, usize),
+    #[display(fmt = "arg {}: expected {}, got {}", _2, _0, _1)]
+    Unexpected(String, &'static str, usize),
+    #[display(fmt = "Too many arguments, expected at most {} but got {}", _0, _1)]
+    Surplus(usize, usize),
     #[display(fmt = "Deserialization error: {}", _0)]
-    Deserialization(String)
+    Deserialization(String),
+    #[display(fmt = "{}", _0)]
+    InvalidArgument(String),
+    #[display(fmt = "arg {}: expected an instance of {}, got {}", _2, _0, _1)]
+    NotInstance(&'static str, &'static str, usize),
+}
+
+#[derive(Display, Debug)]
+pub(crate) enum JsConversionError {
+    #[display(fmt = "{} is out of range for the type {}", _0, _1)]
+    OutOfRange(String, &'static str),
+    #[display(fmt = "{} is not an integer, expected a {}", _0, _1)]
+    NotInteger(f64, &'static str),
+    #[display(fmt = "Expected a string of 1 character, got {:?}", _0)]
+    NotChar(String),
+    #[display(fmt = "Invalid {}: {:?}", _0, _1)]
+    Parse(&'static str, String),
+    #[display(fmt = "The string is not valid UTF-8: {:?}", _0)]
+    NotUtf8(String),
+    #[display(fmt = "Expected an array of length {}, got {}", _0, _1)]
+    Length(usize, usize),
+    #[display(fmt = "Expected a value of type {}, got {}", _0, _1)]
+    WrongType(&'static str, &'static str),
 }
 
 #[derive(Display, Debug, Clone)]
 pub enum JsAnyError {
     #[display(fmt = "Wrong conversion from a JsAny")]
     WrongAny,
+    #[display(fmt = "The value is not iterable")]
+    NotIterable,
 }
 
 impl ArgumentsError {
@@ -103,6 +138,25 @@ impl ArgumentsError {
     pub fn missing(n: usize) -> Error {
         ArgumentsError::Missing(n).into()
     }
+
+    /// Error when the `n`th argument is not the expected type
+    pub fn unexpected(expected: &str, got: &JsAny, n: usize) -> Error {
+        ArgumentsError::Unexpected(expected.to_owned(), got.type_name(), n).into()
+    }
+
+    /// Adds the position of the argument to a deserialization error.
+    ///
+    /// Other errors are returned unchanged.
+    pub(crate) fn locate(error: Error, n: usize) -> Error {
+        let located = match error.downcast_ref::<ArgumentsError>() {
+            Some(ArgumentsError::Deserialization(msg)) if msg.starts_with('.') || msg.starts_with('[') => {
+                format!("arg {} {}", n, msg)
+            }
+            Some(ArgumentsError::Deserialization(msg)) => format!("arg {}: {}", n, msg),
+            _ => return error
+        };
+        ArgumentsError::InvalidArgument(located).into()
+    }
 }
 
 impl<T: JsError + 'static> From<T> for Error {
@@ -123,6 +177,16 @@ impl From<&Error> for Error {
 use std::any::TypeId;
 use std::any::Any;
 
+/// Constructor of the Javascript error thrown from a [`JsError`]
+///
+/// [`JsError`]: ./trait.JsError.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsErrorKind {
+    Error,
+    TypeError,
+    RangeError,
+}
+
 pub trait JsError: JsErrorAsAny + std::fmt::Display + std::fmt::Debug + 'static {
     fn get_msg(&self) -> String {
         format!("{}", self)
@@ -130,6 +194,9 @@ pub trait JsError: JsErrorAsAny + std::fmt::Display + std::fmt::Debug + 'static
     fn get_code(&self) -> Option<String> {
         None
     }
+    fn get_kind(&self) -> JsErrorKind {
+        JsErrorKind::Error
+    }
     fn type_id(&self) -> TypeId {
         TypeId::of::<Self>()
     }
@@ -155,6 +222,13 @@ impl JsError for ArgumentsError {
     fn get_code(&self) -> Option<String> {
         Some("PINAR".to_owned())
     }
+    fn get_kind(&self) -> JsErrorKind {
+        match self {
+            ArgumentsError::Unexpected(..) |
+            ArgumentsError::NotInstance(..) => JsErrorKind::TypeError,
+            _ => JsErrorKind::Error
+        }
+    }
 }
 
 impl JsError for JsExternalError {
@@ -181,10 +255,29 @@ impl JsError for JsFunctionError {
     }
 }
 
+impl JsError for JsConversionError {
+    fn get_code(&self) -> Option<String> {
+        Some("PINAR".to_owned())
+    }
+    fn get_kind(&self) -> JsErrorKind {
+        match self {
+            JsConversionError::OutOfRange(..) |
+            JsConversionError::NotInteger(..) => JsErrorKind::RangeError,
+            _ => JsErrorKind::TypeError
+        }
+    }
+}
+
 impl JsError for JsAnyError {
     fn get_code(&self) -> Option<String> {
         Some("PINAR".to_owned())
     }
+    fn get_kind(&self) -> JsErrorKind {
+        match self {
+            JsAnyError::NotIterable => JsErrorKind::TypeError,
+            _ => JsErrorKind::Error
+        }
+    }
 }
 
 impl JsError for std::io::Error {
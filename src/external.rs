@@ -9,9 +9,11 @@ pub(crate) enum PtrKind<T> {
     Arc(Arc<T>)
 }
 
+/// Must start with the type id: it's read before knowing `T`
+#[repr(C)]
 pub(crate) struct External<T> {
-    pub(crate) ptr: PtrKind<T>,
-    pub(crate) id: TypeId
+    pub(crate) id: TypeId,
+    pub(crate) ptr: PtrKind<T>
 }
 
 impl<T: 'static> External<T> {
@@ -0,0 +1,111 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::error::JsFunctionError;
+use crate::prelude::*;
+
+type NextFn = Box<dyn FnMut(Env) -> JsResult<Option<Value>>>;
+
+/// A Rust iterator converted to a Javascript iterator.
+///
+/// The Javascript object implements the iterator protocol (`next()` and
+/// `return()`) and is iterable (`[Symbol.iterator]`), so it can be used
+/// with `for...of`, the spread syntax, `Array.from`..
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn range(start: i64, end: i64) -> JsIterator {
+///     JsIterator::new(start..end)
+/// }
+///
+/// // From javascript:
+/// // for (const n of rust.range(0, 10)) { .. }
+/// // const array = [...rust.range(0, 10)];
+/// ```
+pub struct JsIterator {
+    next: Rc<RefCell<Option<NextFn>>>
+}
+
+impl JsIterator {
+    /// Creates a `JsIterator` from any value implementing `IntoIterator`.
+    ///
+    /// The items are converted to Javascript when `next()` is called.
+    pub fn new<I>(iter: I) -> JsIterator
+    where
+        I: IntoIterator,
+        I::IntoIter: 'static,
+        I::Item: for<'env> ToJs<'env> + 'static
+    {
+        let mut iter = iter.into_iter();
+        let next: NextFn = Box::new(move |env| {
+            match iter.next() {
+                Some(item) => Ok(Some(item.to_js(env)?.get_value())),
+                _ => Ok(None)
+            }
+        });
+
+        JsIterator {
+            next: Rc::new(RefCell::new(Some(next)))
+        }
+    }
+
+    /// Creates the `{ value, done }` object returned to JS
+    pub(crate) fn result<'e>(env: Env, value: Option<Value>) -> JsResult<JsObject<'e>> {
+        let result = env.object()?;
+
+        match value {
+            Some(value) => {
+                result.set("value", value)?;
+                result.set("done", false)?;
+            }
+            _ => {
+                result.set("value", env.undefined()?)?;
+                result.set("done", true)?;
+            }
+        }
+
+        Ok(result)
+    }
+}
+
+impl<'e> ToJs<'e> for JsIterator {
+    type Value = JsObject<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsObject<'e>> {
+        let object = env.object()?;
+
+        let state = Rc::clone(&self.next);
+        let next = JsClosure::new(move |env: Env| -> JsResult<JsObject> {
+            let mut state = state.try_borrow_mut()
+                                 .map_err(|_| JsFunctionError::ClosureRunning)?;
+
+            let value = match state.as_mut() {
+                Some(next) => next(env)?,
+                _ => None
+            };
+            if value.is_none() {
+                // Drop the iterator once it's done
+                state.take();
+            }
+
+            JsIterator::result(env, value)
+        });
+
+        let state = Rc::clone(&self.next);
+        let ret = JsClosure::new(move |env: Env| -> JsResult<JsObject> {
+            state.try_borrow_mut()
+                 .map_err(|_| JsFunctionError::ClosureRunning)?
+                 .take();
+            JsIterator::result(env, None)
+        });
+
+        let iterator = JsClosure::new(|this: JsThis| this.get_value());
+
+        object.set("next", next)?;
+        object.set("return", ret)?;
+        object.set(env.well_known_symbol("iterator")?, iterator)?;
+
+        Ok(object)
+    }
+}
@@ -15,8 +15,10 @@ use crate::JsResult;
 /// .  
 /// - `Option<T>` where `T:`[`JsReturn`],  
 ///    - None returns a javascript `undefined`.
-/// - `JsResult<T>` where `T:`[`JsReturn`],
+/// - `Result<T, E>` where `T:`[`JsReturn`] and `E:`[`JsError`],
 ///    - the error is thrown in javascript.
+/// - tuples where every elements implement [`ToJs`],
+///    - the tuple is returned as a javascript array.
 ///
 /// # Example
 /// 
@@ -69,30 +71,19 @@ where
     }
 }
 
-impl<'e, T> JsReturn<'e> for Option<T>
+impl<'e, T, E> JsReturn<'e> for Result<T, E>
 where
-    T: JsReturn<'e>
-{
-    fn get_result(self, env: Env) -> Result<Option<Value>, Error> {
-        match self {
-            Some(v) => v.get_result(env),
-            None => Ok(None)
-        }
-    }
-}
-
-impl<'e, T> JsReturn<'e> for JsResult<T>
-where
-    T: JsReturn<'e>
+    T: JsReturn<'e>,
+    E: Into<Error>
 {
     #[cfg(feature = "nightly")] // Make the fn default
     default fn get_result(self, env: Env) -> Result<Option<Value>, Error> {
-        self?.get_result(env)
+        self.map_err(Into::into)?.get_result(env)
     }
 
     #[cfg(not(feature = "nightly"))]
     fn get_result(self, env: Env) -> Result<Option<Value>, Error> {
-        self?.get_result(env)
+        self.map_err(Into::into)?.get_result(env)
     }
 }
 
@@ -109,9 +100,27 @@ where
     }
 }
 
-impl<'e> JsReturn<'e> for () {
-    fn get_result(self, _: Env) -> Result<Option<Value>, Error> {
-        Ok(None)
+// `Option<T>` implements `ToJs` when `T: ToJs`, a generic impl of JsReturn
+// for `Option<T: JsReturn>` would overlap with it.
+// The types implementing JsReturn but not ToJs are listed instead.
+
+fn option_result<'e, T>(option: Option<T>, env: Env) -> Result<Option<Value>, Error>
+where
+    T: JsReturn<'e>
+{
+    match option {
+        Some(v) => v.get_result(env),
+        None => Ok(None)
+    }
+}
+
+impl<'e, T, E> JsReturn<'e> for Option<Result<T, E>>
+where
+    T: JsReturn<'e>,
+    E: Into<Error>
+{
+    fn get_result(self, env: Env) -> Result<Option<Value>, Error> {
+        option_result(self, env)
     }
 }
 
@@ -127,6 +136,15 @@ where
     }
 }
 
+impl<'e, C> JsReturn<'e> for Option<AsJsClass<C>>
+where
+    C: JsClass
+{
+    fn get_result(self, env: Env) -> Result<Option<Value>, Error> {
+        option_result(self, env)
+    }
+}
+
 
 /// JsReturnRef
 ///
@@ -181,18 +199,6 @@ where
     }
 }
 
-impl<'e, T> JsReturnRef<'e> for Option<&T>
-where
-    T: JsReturnRef<'e>
-{
-    fn get_result_from_ref(&self, env: Env) -> Result<Option<Value>, Error> {
-        match self {
-            Some(v) => v.get_result_from_ref(env),
-            None => Ok(None)
-        }
-    }
-}
-
 impl<'e, T> JsReturnRef<'e> for JsResult<&T>
 where
     T: JsReturnRef<'e>
@@ -227,10 +233,3 @@ where
         }
     }
 }
-
-impl<'e> JsReturnRef<'e> for ()
-{
-    fn get_result_from_ref(&self, _env: Env) -> Result<Option<Value>, Error> {
-        Ok(None)
-    }
-}
@@ -115,6 +115,13 @@ mod arguments;
 mod to_rust;
 mod multi_js;
 mod to_js;
+mod closure;
+mod iterator;
+#[cfg(feature = "futures")]
+mod async_iterator;
+mod promise;
+#[cfg(feature = "napi8")]
+mod type_tag;
 
 #[doc(hidden)]
 #[cfg(feature = "pinar-serde")]
@@ -123,20 +130,32 @@ pub mod pinar_serde;
 pub(crate) type Result<R> = std::result::Result<R, Error>;
 pub type JsResult<R> = Result<R>;
 
-pub use crate::error::{Error, JsError};
+pub use crate::error::{Error, JsError, JsErrorKind};
 pub use crate::env::Env;
 pub use crate::multi_js::MultiJs;
 //pub use crate::objects::*;
 //pub use crate::status::Status;
 pub use crate::to_js::ToJs;
 pub use crate::to_rust::ToRust;
+pub use crate::closure::JsClosure;
+pub use crate::iterator::JsIterator;
+#[cfg(feature = "futures")]
+pub use crate::async_iterator::JsAsyncIterator;
+#[cfg(feature = "napi8")]
+pub use crate::type_tag::TypeTag;
 //pub use crate::function_threadsafe::JsFunctionThreadSafe;
 pub use crate::module::ModuleBuilder;
 //pub use crate::property_descriptor::PropertyDescriptor;
 pub use crate::jsreturn::JsReturn;
 //pub use crate::module::__pinar_dispatch_function;
-pub use crate::arguments::{FromArguments, Arguments};
-pub use crate::classes::{JsClass, AsJsClass, ClassBuilder};
+pub use crate::arguments::{FromArguments, Arguments, Signature, ArgSignature, ArgKind, Rest, RawArgs, OrDefault, Iterable};
+pub use crate::classes::{JsClass, AsJsClass, ClassBuilder, ClassRef, ClassBorrow, ClassBorrowMut};
+#[cfg(feature = "pinar-serde")]
+pub use crate::arguments::Options;
+#[cfg(feature = "pinar-serde")]
+pub use crate::pinar_serde::de::{from_any_in, Scratch};
+#[cfg(feature = "pinar-serde")]
+pub use crate::pinar_serde::ser::{serialize_to_js_with, SerializerConfig, NoneAs, BytesAs};
 //pub use crate::JsResult;
 // #[doc(hidden)]
 // #[cfg(feature = "pinar-serde")]
@@ -153,6 +172,12 @@ pub mod prelude {
     pub use crate::status::Status;
     pub use crate::to_js::ToJs;
     pub use crate::to_rust::ToRust;
+    pub use crate::closure::JsClosure;
+    pub use crate::iterator::JsIterator;
+    #[cfg(feature = "futures")]
+    pub use crate::async_iterator::JsAsyncIterator;
+    #[cfg(feature = "napi8")]
+    pub use crate::type_tag::TypeTag;
     //pub use crate::objects::JsFunctionThreadSafe;
     //pub use crate::objects::function_threadsafe::JsFunctionThreadSafe;
     pub use crate::module::ModuleBuilder;
@@ -160,14 +185,20 @@ pub mod prelude {
     pub use crate::property_descriptor::PropertyDescriptor;
     pub use crate::jsreturn::{JsReturn, JsReturnRef};
     //pub use crate::module::__pinar_dispatch_function;
-    pub use crate::arguments::{FromArguments, Arguments};
-    pub use crate::classes::{JsClass, AsJsClass, ClassBuilder};
+    pub use crate::arguments::{FromArguments, Arguments, Signature, ArgSignature, ArgKind, Rest, RawArgs, OrDefault, Iterable};
+    pub use crate::classes::{JsClass, AsJsClass, ClassBuilder, ClassRef, ClassBorrow, ClassBorrowMut};
+    #[cfg(feature = "pinar-serde")]
+    pub use crate::arguments::Options;
     #[doc(inline)]
     pub use crate::JsResult;
     #[doc(hidden)]
     #[cfg(feature = "pinar-serde")]
     pub use crate::pinar_serde::ser::serialize_to_js;
     #[cfg(feature = "pinar-serde")]
+    pub use crate::pinar_serde::ser::{serialize_to_js_with, SerializerConfig, NoneAs, BytesAs};
+    #[cfg(feature = "pinar-serde")]
+    pub use crate::pinar_serde::de::{from_any_in, Scratch};
+    #[cfg(feature = "pinar-serde")]
     pub use pinar_derive::Pinar;
     // #[cfg(feature = "pinar-serde")]
     // pub use pinar_derive;
@@ -176,7 +207,7 @@ pub mod prelude {
     // pub use super::register_module;
     #[doc(hidden)]
     pub use napi_sys::{napi_env, napi_value};
-    pub use crate::error::{ArgumentsError, JsAnyError, JsError};
+    pub use crate::error::{ArgumentsError, JsAnyError, JsError, JsErrorKind};
 
     #[doc(hidden)]
     pub use linkme::distributed_slice;
@@ -19,18 +19,69 @@ pub struct ModuleBuilder<'e> {
     env: Env,
     export: JsObject<'e>,
     functions: HashMap<String, ModuleFunction>,
-    classes: Vec<(&'static str, JsFunction<'e>)>
+    classes: Vec<(&'static str, JsFunction<'e>)>,
+    strict: bool
 }
 
 /// Contains function(s) ptr and its name.
 ///
 /// There can be more than 1 function to allow functions overloading
 pub(crate) struct ModuleFunction {
-    pub(crate) functions: Vec<Box<dyn CallbackHandler>>,
-    name: String
+    pub(crate) functions: Vec<Overload>,
+    name: String,
+    /// In strict mode, calling the function with surplus arguments is an error
+    strict: bool
+}
+
+/// An overload of a function
+pub(crate) struct Overload {
+    handler: Box<dyn CallbackHandler>,
+    /// Strict mode of this overload only, see `ModuleFunction::strict`
+    strict: bool
 }
 
 impl ModuleFunction {
+    /// Selects the overload matching the arguments and calls it.
+    ///
+    /// The overloads are first filtered by inspecting the javascript values
+    /// against their [`Signature`], without converting anything.  
+    /// The remaining ones are tried in order, the overloads consuming all the
+    /// arguments first. An overload whose arguments conversion fails is skipped
+    /// without being called.
+    ///
+    /// [`Signature`]: ../struct.Signature.html
+    fn call_overload(&self, args: &Arguments) -> JsResult<Option<Value>> {
+        let mut reasons: Vec<Option<String>> = vec![None; self.functions.len()];
+
+        let mut candidates: Vec<_> = self.functions.iter().enumerate().filter(|(index, function)| {
+            match function.handler.signature().check(args, self.is_strict(function)) {
+                Ok(_) => true,
+                Err(reason) => {
+                    reasons[*index] = Some(reason);
+                    false
+                }
+            }
+        }).collect();
+
+        candidates.sort_by_key(|(_, function)| {
+            matches!(function.handler.signature().max_args(), Some(max) if max < args.len())
+        });
+
+        for (index, function) in candidates {
+            args.reset();
+            match function.handler.try_call(args, self.is_strict(function)) {
+                Ok(result) => return result,
+                Err(e) => reasons[index] = Some(e.as_js_error().get_msg())
+            }
+        }
+
+        let reasons: Vec<_> = self.functions.iter().zip(reasons).map(|(function, reason)| {
+            format!("  {}{}: {}", self.name, function.handler.signature(), reason.unwrap_or_default())
+        }).collect();
+
+        Err(JsFunctionError::ArgumentsOverload(self.name.clone(), reasons.join("\n")).into())
+    }
+
     pub(crate) fn new<N, Fun, Args, R>(name: N, fun: Fun) -> ModuleFunction
     where
         N: Into<String>,
@@ -40,7 +91,26 @@ impl ModuleFunction {
     {
         ModuleFunction {
             name: name.into(),
-            functions: vec![Box::new(fun.make())]
+            functions: vec![Overload::new(fun, false)],
+            strict: false
+        }
+    }
+
+    fn is_strict(&self, overload: &Overload) -> bool {
+        self.strict || overload.strict
+    }
+}
+
+impl Overload {
+    fn new<Fun, Args, R>(fun: Fun, strict: bool) -> Overload
+    where
+        Fun: CallbackFn<Args, R> + 'static,
+        Args: FromArguments + 'static,
+        R: for<'env> JsReturn<'env> + 'static
+    {
+        Overload {
+            handler: Box::new(fun.make()),
+            strict
         }
     }
 }
@@ -53,10 +123,33 @@ impl<'e> ModuleBuilder<'e> {
             env,
             export: JsObject::from(export),
             functions: HashMap::new(),
-            classes: vec![]
+            classes: vec![],
+            strict: false
         }
     }
 
+    /// Enables the strict mode on all the exported functions.
+    ///
+    /// In strict mode, calling a function with more arguments than it
+    /// accepts throws an error instead of ignoring the surplus arguments.  
+    /// It applies to the functions of the module only: class methods, static
+    /// methods and closures always ignore surplus arguments.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[distributed_slice(PINAR_FUNCTIONS)]
+    /// static STRICT_MODE: fn(&mut ModuleBuilder) = {
+    ///     fn init(builder: &mut ModuleBuilder) {
+    ///         builder.strict_arguments(true);
+    ///     }
+    ///     init
+    /// };
+    /// ```
+    pub fn strict_arguments(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
     /// Add a function to export
     pub fn with_function<S, Fun, Args, R>(&mut self, name: S, fun: Fun)
     where
@@ -65,13 +158,39 @@ impl<'e> ModuleBuilder<'e> {
         Args: FromArguments + 'static,
         R: for<'env> JsReturn<'env> + 'static
     {
-        let name = name.into();
+        self.add_function(name.into(), Overload::new(fun, false));
+    }
+
+    /// Similar to [`with_function`] but the function is in strict mode:  
+    /// calling it with surplus arguments throws an error.
+    ///
+    /// With overloads, only this overload is in strict mode: the others
+    /// still accept surplus arguments.  
+    /// It applies to the functions of the module only, see [`strict_arguments`].
+    ///
+    /// [`with_function`]: #method.with_function
+    /// [`strict_arguments`]: #method.strict_arguments
+    pub fn with_strict_function<S, Fun, Args, R>(&mut self, name: S, fun: Fun)
+    where
+        S: Into<String>,
+        Fun: CallbackFn<Args, R> + 'static,
+        Args: FromArguments + 'static,
+        R: for<'env> JsReturn<'env> + 'static
+    {
+        self.add_function(name.into(), Overload::new(fun, true));
+    }
+
+    fn add_function(&mut self, name: String, overload: Overload) {
         match self.functions.entry(name.clone()) {
             Entry::Occupied(mut funs) => {
-                funs.get_mut().functions.push(Box::new(fun.make()));
+                funs.get_mut().functions.push(overload);
             }
             Entry::Vacant(funs) => {
-                funs.insert(ModuleFunction::new(name, fun));
+                funs.insert(ModuleFunction {
+                    name,
+                    functions: vec![overload],
+                    strict: false
+                });
             }
         };
     }
@@ -80,13 +199,14 @@ impl<'e> ModuleBuilder<'e> {
     pub fn with_class<C: 'static +  JsClass>(&mut self) {
         self.classes.push((
             C::CLASSNAME,
-            ClassBuilder::<C>::default().create(&self.env).unwrap()
+            ClassBuilder::<C>::constructor(&self.env).unwrap()
         ));
     }
 
     /// Build the `js module`
     pub fn build(self) -> JsResult<napi_value> {
-        for (name, functions) in self.functions.into_iter() {
+        for (name, mut functions) in self.functions.into_iter() {
+            functions.strict |= self.strict;
             let fun = Rc::new(functions);
             let jsfunction = self.env.function_internal(&name, fun)?;
 
@@ -120,24 +240,12 @@ pub(crate) extern "C" fn __pinar_dispatch_function(env: napi_env, info: napi_cal
             return Err(JsFunctionError::WrongFunctionData.into());
         }
 
-        let function = unsafe { &mut *fun };
-        let mut last_error = None;
+        let function = unsafe { &*fun };
 
-        for function in &function.functions {
-            let result = function.as_ref().call(&args);
-
-            if let Err(e) = result {
-                last_error = Some(e);
-                continue;
-            }
-
-            return result;
-        }
-
-        match function.functions.len() {
-            0 => Err(JsFunctionError::WrongFunctionData.into()),
-            1 if last_error.is_some() => Err(last_error.unwrap()),
-            _ => Err(JsFunctionError::ArgumentsOverload(function.name.clone()).into())
+        match function.functions.as_slice() {
+            [] => Err(JsFunctionError::WrongFunctionData.into()),
+            [single] => single.handler.call(&args, function.is_strict(single)),
+            _ => function.call_overload(&args)
         }
     })
 }
@@ -148,7 +256,8 @@ where
     A: FromArguments,
     R: for<'env> JsReturn<'env>
 {
-    fun: Box<dyn Fn(A) -> R>
+    fun: Box<dyn Fn(A) -> R>,
+    signature: Signature
 }
 
 impl<A, R> Callback<A, R>
@@ -161,7 +270,8 @@ where
         F: Fn(A) -> R + 'static
     {
         Callback {
-            fun: Box::new(fun)
+            fun: Box::new(fun),
+            signature: Signature::of::<A>()
         }
     }
 }
@@ -173,7 +283,16 @@ where
 /// - Call the function and return its result
 ///
 pub(crate) trait CallbackHandler {
-    fn call(&self, args: &Arguments) -> JsResult<Option<Value>>;
+    fn call(&self, args: &Arguments, strict: bool) -> JsResult<Option<Value>> {
+        self.try_call(args, strict)?
+    }
+
+    /// Similar to `call` but the outer result is an error when the arguments
+    /// can't be converted, in which case the function is not called.
+    fn try_call(&self, args: &Arguments, strict: bool) -> JsResult<JsResult<Option<Value>>>;
+
+    /// Returns the javascript arguments expected by the function
+    fn signature(&self) -> &Signature;
 }
 
 impl<A, R> CallbackHandler for Callback<A, R>
@@ -182,11 +301,19 @@ where
     R: for<'env> JsReturn<'env>
 {
     /// See [`CallbackHandler`]
-    fn call(&self, args: &Arguments) -> JsResult<Option<Value>> {
+    fn try_call(&self, args: &Arguments, strict: bool) -> JsResult<JsResult<Option<Value>>> {
         let env = args.env();
-        let args = A::from_args(args)?;
+        let converted = A::from_args(args)?;
+
+        if strict {
+            args.check_surplus()?;
+        }
+
+        Ok((self.fun)(converted).get_result(env))
+    }
 
-        (self.fun)(args).get_result(env)
+    fn signature(&self) -> &Signature {
+        &self.signature
     }
 }
 
@@ -4,30 +4,100 @@ use crate::*;
 /// Trait to convert one or multiple value(s) to javascript.
 ///
 /// Is it implemented for
-/// - `T` where `T:`[`ToJs`]
-/// - tuples where every elements implement [`ToJs`]
+/// - tuples where every elements implement [`ToJs`], each element is an argument
+/// - the types of pinar implementing [`ToJs`], converted to a single argument
+/// - `&T` where `T:`[`ToJs`]
 ///
+/// Tuples implement [`ToJs`] as arrays: to pass an array as a single
+/// argument, use `(tuple,)`.  
+/// Other types implementing [`ToJs`] are passed by reference:
+///
+/// ```
+/// fun.call(&my_struct)?;
+/// fun.call((&my_struct, 10))?;
+/// ```
+///
+/// [`ToJs`]: ./trait.ToJs.html
 pub trait MultiJs {
     #[doc(hidden)]
     fn make_values(self, env: Env) -> JsResult<MultiValue>;
 }
 
-impl<'e, A> MultiJs for A
-where
-    A: ToJs<'e>,
-{
-    fn make_values(self, env: Env) -> JsResult<MultiValue> {
-        let mut values: [napi_value; 12] = [std::ptr::null_mut(); 12];
+macro_rules! multi_js_single {
+    (
+        $( [ $($gen:tt)* ] $type:ty ),*
+    ) => {
+        $(
+            impl<'e, $($gen)*> MultiJs for $type
+            where
+                $type: ToJs<'e>
+            {
+                fn make_values(self, env: Env) -> JsResult<MultiValue> {
+                    let mut values: [napi_value; 12] = [std::ptr::null_mut(); 12];
 
-        values[0] = self.to_js(env)?.get_value().value;
+                    values[0] = self.to_js(env)?.get_value().value;
 
-        Ok(MultiValue {
-            values,
-            len: 1
-        })
+                    Ok(MultiValue {
+                        values,
+                        len: 1
+                    })
+                }
+            }
+        )*
     }
 }
 
+multi_js_single!(
+    ['v] JsString<'v>,
+    ['v] JsObject<'v>,
+    ['v] JsArray<'v>,
+    ['v] JsNumber<'v>,
+    ['v] JsSymbol<'v>,
+    ['v] JsUndefined<'v>,
+    ['v] JsFunction<'v>,
+    ['v] JsExternal<'v>,
+    ['v] JsNull<'v>,
+    ['v] JsBoolean<'v>,
+    ['v] JsBigInt<'v>,
+    ['v] JsMap<'v>,
+    ['v] JsSet<'v>,
+    ['v] JsAny<'v>,
+    [] Value,
+    [] i8, [] i16, [] i32, [] i64, [] i128, [] isize,
+    [] u8, [] u16, [] u32, [] u64, [] u128, [] usize,
+    [] f32, [] f64,
+    [] char,
+    [] bool,
+    [] String,
+    [] std::time::Duration,
+    [] std::net::IpAddr,
+    [] std::net::Ipv4Addr,
+    [] std::net::Ipv6Addr,
+    [] std::net::SocketAddr,
+    [] std::ffi::OsString,
+    [] std::path::PathBuf,
+    ['c] std::borrow::Cow<'c, str>,
+    [T] Option<T>,
+    [T] Vec<T>,
+    [T] std::collections::VecDeque<T>,
+    [T] Box<[T]>,
+    [K, V, S] std::collections::HashMap<K, V, S>,
+    [K, V] std::collections::BTreeMap<K, V>,
+    [T] AsMap<T>,
+    [T] AsSet<T>,
+    [T] std::rc::Rc<T>,
+    [T] std::sync::Arc<T>,
+    ['r, T: ?Sized] &'r T,
+    [] JsClosure,
+    [] JsIterator
+);
+
+#[cfg(feature = "json")]
+multi_js_single!([] serde_json::Value);
+
+#[cfg(feature = "futures")]
+multi_js_single!([] JsAsyncIterator);
+
 /// Opaque structure containing multiple javascript values
 pub struct MultiValue {
     values: [napi_value; 12],
@@ -6,6 +6,8 @@ use std::rc::Rc;
 use napi_sys::*;
 use crate::prelude::*;
 use crate::external::External;
+#[cfg(feature = "napi8")]
+use crate::type_tag::{TypeTag, tag_value, has_tag};
 
 /// A Javascript external value.  
 ///   
@@ -60,7 +62,16 @@ pub struct JsExternal<'e> {
 
 impl<'e> JsExternal<'e> {
     /// Retrieves the raw `External` from a `JsExternal`
-    fn get_external<T>(&self) -> JsResult<*mut External<T>> {
+    fn get_external<T: 'static>(&self) -> JsResult<*mut External<T>> {
+        let wrong_type = || JsExternalError::WrongType(std::any::type_name::<T>()).into();
+
+        #[cfg(feature = "napi8")]
+        {
+            if !self.has_type_tag(TypeTag::of::<External<T>>())? {
+                return Err(wrong_type())
+            }
+        }
+
         let mut external: *mut External<T> = std::ptr::null_mut();
         napi_call!(napi_get_value_external(
             self.value.env(),
@@ -68,8 +79,17 @@ impl<'e> JsExternal<'e> {
             &mut external as *mut *mut External<T> as *mut *mut std::ffi::c_void
         ))?;
         if external.is_null() {
-            return Err(JsExternalError.into())
+            return Err(JsExternalError::NullPointer.into())
         }
+
+        #[cfg(not(feature = "napi8"))]
+        {
+            // The type id is the first field of External, whatever its type
+            if unsafe { *(external as *const std::any::TypeId) } != std::any::TypeId::of::<T>() {
+                return Err(wrong_type())
+            }
+        }
+
         Ok(external)
     }
 
@@ -90,14 +110,15 @@ impl<'e> JsExternal<'e> {
     ///     // This panics, the external value is not a Rc
     ///     
     ///     external.take_box::<usize>()?;
-    ///     // This panics, the external value is not a usize
+    ///     // This returns an error, the external value is not a usize
     ///
     ///     Ok(())
     /// }
     /// ```
+    /// # Errors
+    /// Returns an error if the type of the Box is different
     /// # Panics
-    /// This function panics if the type of the Box is different, or if
-    /// the external value is not a Box (Rc, or Arc)
+    /// This function panics if the external value is not a Box (Rc, or Arc)
     pub fn take_box<T: 'static>(&self) -> JsResult<Option<Box<T>>> {
         let external = self.get_external::<T>()?;
         // Deref raw pointer is unsafe
@@ -117,14 +138,15 @@ impl<'e> JsExternal<'e> {
     ///     // value is another Rc<usize>
     ///     
     ///     external.take_box::<usize>()?; // panics, it's not a Box
-    ///     external.get_rc::<String>()?; // panics, it's not a Rc<String>
+    ///     external.get_rc::<String>()?; // error, it's not a Rc<String>
     ///
     ///     Ok(())
     /// }
     /// ```
+    /// # Errors
+    /// Returns an error if the type of the Rc is different
     /// # Panics
-    /// This function panics if the type of the Rc is different, or if
-    /// the external value is not a Rc (Box, or Arc)
+    /// This function panics if the external value is not a Rc (Box, or Arc)
     pub fn get_rc<T: 'static>(&self) -> JsResult<Rc<T>> {
         let external = self.get_external::<T>()?;
         // Deref raw pointer is unsafe
@@ -144,17 +166,36 @@ impl<'e> JsExternal<'e> {
     ///     // value is another Arc<usize>
     ///     
     ///     external.take_rc::<usize>()?; // panics, it's not a Rc
-    ///     external.get_arc::<String>()?; // panics, it's not an Arc<String>
+    ///     external.get_arc::<String>()?; // error, it's not an Arc<String>
     ///
     ///     Ok(())
     /// }
     /// ```
+    /// # Errors
+    /// Returns an error if the type of the Arc is different
     /// # Panics
-    /// This function panics if the type of the Arc is different, or if
-    /// the external value is not a Arc (Box, or Rc)
+    /// This function panics if the external value is not a Arc (Box, or Rc)
     pub fn get_arc<T: 'static>(&self) -> JsResult<Arc<T>> {
         let external = self.get_external::<T>()?;
         // Deref raw pointer is unsafe
         Ok(unsafe { (*external).get_arc::<T>() })
     }
+
+    /// Tags the external value, see [`TypeTag`].
+    ///
+    /// Requires the `napi8` feature.
+    ///
+    /// Externals created by pinar are already tagged with their Rust type.
+    ///
+    /// [`TypeTag`]: ./struct.TypeTag.html
+    #[cfg(feature = "napi8")]
+    pub fn type_tag(&self, tag: TypeTag) -> JsResult<()> {
+        tag_value(self.get_value(), tag)
+    }
+
+    /// Returns true if the external value has been tagged with `tag`
+    #[cfg(feature = "napi8")]
+    pub fn has_type_tag(&self, tag: TypeTag) -> JsResult<bool> {
+        has_tag(self.get_value(), tag)
+    }
 }
@@ -0,0 +1,190 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::Error;
+use crate::error::JsFunctionError;
+use crate::promise::Deferred;
+use crate::prelude::*;
+
+/// Iterator over the values of a javascript iterable.
+///
+/// This struct is created by the [`iter_js`] method on [`JsObject`].
+///
+/// Each item is a `JsResult`, the iterator `next()` can throw.
+/// When dropped before its end, the iterator `return()` method is called,
+/// similar to a `break` in a `for...of` loop.
+///
+/// [`iter_js`]: struct.JsObject.html#method.iter_js
+/// [`JsObject`]: struct.JsObject.html
+pub struct JsObjectIterator<'e> {
+    iterator: JsObject<'e>,
+    next: JsFunction<'e>,
+    done: bool,
+}
+
+impl<'e> JsObjectIterator<'e> {
+    pub(crate) fn new(iterator: JsObject<'e>) -> JsResult<JsObjectIterator<'e>> {
+        let next = iterator.get("next")?.as_jsfunction()?;
+        Ok(JsObjectIterator { iterator, next, done: false })
+    }
+
+    fn step(&self) -> JsResult<Option<JsAny<'e>>> {
+        let result = self.next.call_with_this(self.iterator.get_value(), ())?;
+        let result = result.as_jsobject()?;
+
+        match is_done(&result)? {
+            true => Ok(None),
+            _ => Ok(Some(result.get("value")?))
+        }
+    }
+}
+
+impl<'e> Iterator for JsObjectIterator<'e> {
+    type Item = JsResult<JsAny<'e>>;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        match self.step() {
+            Ok(Some(value)) => Some(Ok(value)),
+            Ok(None) => {
+                self.done = true;
+                None
+            }
+            Err(error) => {
+                self.done = true;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+impl<'e> Drop for JsObjectIterator<'e> {
+    fn drop(&mut self) {
+        if !self.done {
+            close(&self.iterator);
+        }
+    }
+}
+
+/// Reads the `done` property of an iterator result
+fn is_done(result: &JsObject) -> JsResult<bool> {
+    match result.get("done")? {
+        JsAny::Boolean(done) => done.to_rust(),
+        _ => Ok(false)
+    }
+}
+
+/// Calls the `return()` method of the iterator, if any
+fn close(iterator: &JsObject) {
+    if let Ok(JsAny::Function(ret)) = iterator.get("return") {
+        let _ = ret.call_with_this(iterator.get_value(), ());
+    }
+}
+
+/// State of a `for await` loop started by [`JsObject::for_await`]
+///
+/// Each step waits on the promise returned by the iterator `next()`
+/// and calls the Rust function with its value.
+///
+/// [`JsObject::for_await`]: struct.JsObject.html#method.for_await
+pub(crate) struct ForAwait<F> {
+    iterator: JsRef<JsObject<'static>>,
+    next: JsRef<JsFunction<'static>>,
+    fun: RefCell<F>,
+    deferred: RefCell<Option<Deferred>>,
+}
+
+impl<F> ForAwait<F>
+where
+    F: FnMut(JsAny) -> JsResult<()> + 'static
+{
+    /// Starts the loop and returns the promise settled at its end
+    pub(crate) fn start(env: Env, iterator: JsObject, fun: F) -> JsResult<Value> {
+        let next = iterator.get("next")?.as_jsfunction()?;
+        let (deferred, promise) = Deferred::new(env)?;
+
+        let state = Rc::new(ForAwait {
+            iterator: iterator.as_js_ref()?,
+            next: next.as_js_ref()?,
+            fun: RefCell::new(fun),
+            deferred: RefCell::new(Some(deferred)),
+        });
+        ForAwait::step(&state, env);
+
+        Ok(promise)
+    }
+
+    fn step(state: &Rc<Self>, env: Env) {
+        if let Err(error) = ForAwait::wait_next(state, env) {
+            state.reject(env, &error);
+        }
+    }
+
+    /// Calls `next()` and waits for its result with `Promise.resolve(result).then(..)`
+    fn wait_next(state: &Rc<Self>, env: Env) -> JsResult<()> {
+        let iterator = state.iterator.deref()?;
+        let result = state.next.deref()?.call_with_this(iterator.get_value(), ())?;
+
+        let promise = env.global()?.get("Promise")?.as_jsfunction()?;
+        let resolve = JsObject::from(promise.get_value()).get("resolve")?.as_jsfunction()?;
+        let result = resolve.call_with_this(promise.get_value(), result.get_value())?.as_jsobject()?;
+        let then = result.get("then")?.as_jsfunction()?;
+
+        let fulfilled_state = Rc::clone(state);
+        let fulfilled = JsClosure::new(move |(env, result): (Env, Value)| {
+            ForAwait::fulfilled(&fulfilled_state, env, result);
+        });
+
+        let rejected_state = Rc::clone(state);
+        let rejected = JsClosure::new(move |(env, error): (Env, Value)| {
+            if let Some(deferred) = rejected_state.deferred.borrow_mut().take() {
+                let _ = deferred.reject(env, error);
+            }
+        });
+
+        then.call_with_this(result.get_value(), (fulfilled, rejected))?;
+        Ok(())
+    }
+
+    fn fulfilled(state: &Rc<Self>, env: Env, result: Value) {
+        match state.call(result) {
+            Ok(true) => {
+                if let Some(deferred) = state.deferred.borrow_mut().take() {
+                    let _ = env.undefined().and_then(|undefined| {
+                        deferred.resolve(env, undefined.get_value())
+                    });
+                }
+            }
+            Ok(false) => ForAwait::step(state, env),
+            Err(error) => {
+                // Rejecting first clears a pending exception
+                state.reject(env, &error);
+                if let Ok(iterator) = state.iterator.deref() {
+                    close(&iterator);
+                }
+            }
+        }
+    }
+
+    /// Calls the Rust function with the value of the iterator result.
+    /// Returns true when the iterator is done.
+    fn call(&self, result: Value) -> JsResult<bool> {
+        let result = JsAny::from(result)?.as_jsobject()?;
+        if is_done(&result)? {
+            return Ok(true);
+        }
+
+        let mut fun = self.fun.try_borrow_mut()
+                               .map_err(|_| JsFunctionError::ClosureRunning)?;
+        (*fun)(result.get("value")?)?;
+
+        Ok(false)
+    }
+
+    fn reject(&self, env: Env, error: &Error) {
+        if let Some(deferred) = self.deferred.borrow_mut().take() {
+            let _ = env.error_to_js(error).and_then(|error| deferred.reject(env, error));
+        }
+    }
+}
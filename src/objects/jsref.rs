@@ -165,5 +165,7 @@ impl_jsref!(
     JsExternal,
     JsNull,
     JsBoolean,
-    JsBigInt
+    JsBigInt,
+    JsMap,
+    JsSet
 );
@@ -0,0 +1,299 @@
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use napi_sys::*;
+use crate::prelude::*;
+
+/// A Javascript `Map`.
+///
+/// Unlike a plain object, the keys of a `Map` can be any javascript value
+/// (numbers, objects, ..).
+///
+/// Rust maps are converted to a `JsMap` with the wrapper [`AsMap`].
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(env: Env) -> JsResult<JsMap> {
+///     let map = JsMap::new(env)?;
+///     map.set(1, "one")?;
+///     map.set(env.object()?, "an object as key")?;
+///     Ok(map)
+/// }
+/// ```
+///
+/// [`AsMap`]: ./struct.AsMap.html
+pub struct JsMap<'e> {
+    pub(crate) value: Value,
+    pub(crate) phantom: PhantomData<&'e ()>
+}
+
+/// A Javascript `Set`.
+///
+/// Rust sets are converted to a `JsSet` with the wrapper [`AsSet`].
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(env: Env) -> JsResult<JsSet> {
+///     let set = JsSet::new(env)?;
+///     set.add("a")?;
+///     set.add(10)?;
+///     Ok(set)
+/// }
+/// ```
+///
+/// [`AsSet`]: ./struct.AsSet.html
+pub struct JsSet<'e> {
+    pub(crate) value: Value,
+    pub(crate) phantom: PhantomData<&'e ()>
+}
+
+/// Creates a new instance of the global constructor `name`
+fn new_instance(env: Env, name: &str) -> JsResult<Value> {
+    let constructor = env.global()?.get(name)?.as_jsfunction()?;
+    Ok(constructor.new_instance(())?.get_value())
+}
+
+/// Checks if `value` is an instance of the global constructor `name`
+pub(crate) fn is_instance_of(value: Value, name: &str) -> JsResult<bool> {
+    let constructor = value.env.global()?.get(name)?.as_jsfunction()?;
+    let mut result = false;
+
+    napi_call!(napi_instanceof(
+        value.env(),
+        value.get(),
+        constructor.get_value().get(),
+        &mut result
+    ))?;
+
+    Ok(result)
+}
+
+/// Calls the method `name` of the object
+fn call_method<'e>(value: Value, name: &str, args: impl MultiJs) -> JsResult<JsAny<'e>> {
+    let object = JsObject::from(value);
+    let method = object.get(name)?.as_jsfunction()?;
+    method.call_with_this(value, args)
+}
+
+/// Reads the boolean returned by a method
+fn to_bool(any: JsAny) -> JsResult<bool> {
+    match any {
+        JsAny::Boolean(b) => b.to_rust(),
+        _ => Ok(false)
+    }
+}
+
+impl<'e> JsMap<'e> {
+    /// Creates an empty `Map`
+    pub fn new(env: Env) -> JsResult<JsMap<'e>> {
+        Ok(JsMap::from(new_instance(env, "Map")?))
+    }
+
+    /// Converts the object to a `JsMap`.
+    /// Returns `None` if the object is not a `Map`.
+    pub fn from_object(object: &JsObject<'e>) -> JsResult<Option<JsMap<'e>>> {
+        match is_instance_of(object.get_value(), "Map")? {
+            true => Ok(Some(JsMap::from(object.get_value()))),
+            _ => Ok(None)
+        }
+    }
+
+    /// Returns the number of entries, the `size` of the map
+    pub fn len(&self) -> JsResult<usize> {
+        let size: i64 = JsObject::from(self.value).get("size")?.as_number()?;
+        Ok(size as usize)
+    }
+
+    /// Returns true if the map has no entry
+    pub fn is_empty(&self) -> JsResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Sets the value for the key
+    pub fn set<K, V>(&self, key: K, value: V) -> JsResult<()>
+    where
+        K: ToJs<'e>,
+        V: ToJs<'e>
+    {
+        call_method(self.value, "set", (key, value))?;
+        Ok(())
+    }
+
+    /// Returns the value associated to the key, `undefined` if there is none
+    pub fn get<K>(&self, key: K) -> JsResult<JsAny<'e>>
+    where
+        K: ToJs<'e>
+    {
+        call_method(self.value, "get", (key,))
+    }
+
+    /// Checks if the map has the key
+    pub fn has<K>(&self, key: K) -> JsResult<bool>
+    where
+        K: ToJs<'e>
+    {
+        to_bool(call_method(self.value, "has", (key,))?)
+    }
+
+    /// Removes the key from the map.
+    /// Returns true if the key was present.
+    pub fn delete<K>(&self, key: K) -> JsResult<bool>
+    where
+        K: ToJs<'e>
+    {
+        to_bool(call_method(self.value, "delete", (key,))?)
+    }
+
+    /// Removes all entries
+    pub fn clear(&self) -> JsResult<()> {
+        call_method(self.value, "clear", ())?;
+        Ok(())
+    }
+
+    /// Returns the entries of the map, in insertion order
+    pub fn entries(&self) -> JsResult<Vec<(JsAny<'e>, JsAny<'e>)>> {
+        JsObject::from(self.value)
+            .iter_js()?
+            .map(|entry| {
+                let entry = entry?.as_jsarray()?;
+                Ok((entry.get(0)?, entry.get(1)?))
+            })
+            .collect()
+    }
+}
+
+impl<'e> JsSet<'e> {
+    /// Creates an empty `Set`
+    pub fn new(env: Env) -> JsResult<JsSet<'e>> {
+        Ok(JsSet::from(new_instance(env, "Set")?))
+    }
+
+    /// Converts the object to a `JsSet`.
+    /// Returns `None` if the object is not a `Set`.
+    pub fn from_object(object: &JsObject<'e>) -> JsResult<Option<JsSet<'e>>> {
+        match is_instance_of(object.get_value(), "Set")? {
+            true => Ok(Some(JsSet::from(object.get_value()))),
+            _ => Ok(None)
+        }
+    }
+
+    /// Returns the number of values, the `size` of the set
+    pub fn len(&self) -> JsResult<usize> {
+        let size: i64 = JsObject::from(self.value).get("size")?.as_number()?;
+        Ok(size as usize)
+    }
+
+    /// Returns true if the set has no value
+    pub fn is_empty(&self) -> JsResult<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Adds a value to the set
+    pub fn add<V>(&self, value: V) -> JsResult<()>
+    where
+        V: ToJs<'e>
+    {
+        call_method(self.value, "add", (value,))?;
+        Ok(())
+    }
+
+    /// Checks if the set contains the value
+    pub fn has<V>(&self, value: V) -> JsResult<bool>
+    where
+        V: ToJs<'e>
+    {
+        to_bool(call_method(self.value, "has", (value,))?)
+    }
+
+    /// Removes the value from the set.
+    /// Returns true if the value was present.
+    pub fn delete<V>(&self, value: V) -> JsResult<bool>
+    where
+        V: ToJs<'e>
+    {
+        to_bool(call_method(self.value, "delete", (value,))?)
+    }
+
+    /// Removes all values
+    pub fn clear(&self) -> JsResult<()> {
+        call_method(self.value, "clear", ())?;
+        Ok(())
+    }
+
+    /// Returns the values of the set, in insertion order
+    pub fn values(&self) -> JsResult<Vec<JsAny<'e>>> {
+        JsObject::from(self.value).iter_js()?.collect()
+    }
+}
+
+/// Wrapper to convert a Rust map to and from a Javascript `Map`.
+///
+/// By default, `HashMap` is converted to a plain object: its keys have
+/// to be strings or numbers.
+/// With `AsMap`, `HashMap` and `BTreeMap` are converted to a `Map`, which accepts
+/// any key.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(map: AsMap<HashMap<i64, String>>) -> AsMap<BTreeMap<String, i64>> {
+///     let map = map.into_inner();
+///     AsMap(map.into_iter().map(|(k, v)| (v, k)).collect())
+/// }
+/// ```
+pub struct AsMap<T>(pub T);
+
+/// Wrapper to convert a Rust set to and from a Javascript `Set`.
+///
+/// By default, sets are not convertible.
+/// With `AsSet`, `HashSet` and `BTreeSet` are converted to a `Set`.
+///
+/// # Example
+///
+/// ```
+/// #[pinar]
+/// fn my_func(set: AsSet<HashSet<String>>) -> AsSet<BTreeSet<String>> {
+///     AsSet(set.into_inner().into_iter().collect())
+/// }
+/// ```
+pub struct AsSet<T>(pub T);
+
+macro_rules! impl_wrapper {
+    (
+        $( $wrapper:ident ),*
+    ) => {
+        $(
+            impl<T> $wrapper<T> {
+                /// Returns the inner collection
+                pub fn into_inner(self) -> T {
+                    self.0
+                }
+            }
+
+            impl<T> Deref for $wrapper<T> {
+                type Target = T;
+                fn deref(&self) -> &T {
+                    &self.0
+                }
+            }
+
+            impl<T> DerefMut for $wrapper<T> {
+                fn deref_mut(&mut self) -> &mut T {
+                    &mut self.0
+                }
+            }
+
+            impl<T> From<T> for $wrapper<T> {
+                fn from(inner: T) -> $wrapper<T> {
+                    $wrapper(inner)
+                }
+            }
+        )*
+    }
+}
+
+impl_wrapper!(AsMap, AsSet);
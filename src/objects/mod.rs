@@ -12,7 +12,9 @@ use crate::env::Env;
 mod array;
 mod external;
 mod function;
+mod iterable;
 mod jsref;
+mod map;
 mod number;
 mod object;
 mod string;
@@ -32,6 +34,13 @@ pub use self::{
     external::JsExternal,
     function::JsFunction,
     function_threadsafe::JsFunctionThreadSafe,
+    map::{
+        JsMap,
+        JsSet,
+        AsMap,
+        AsSet,
+    },
+    iterable::JsObjectIterator,
     number::JsNumber,
     object::{
         JsObject,
@@ -169,8 +178,24 @@ impl<'e> JsAny<'e> {
         }
     }
 
+    /// Returns the name of the javascript type, as used in error messages
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            JsAny::String(_) => "string",
+            JsAny::Object(_) => "object",
+            JsAny::Array(_) => "array",
+            JsAny::Number(_) => "number",
+            JsAny::Symbol(_) => "symbol",
+            JsAny::External(_) => "external",
+            JsAny::Function(_) => "function",
+            JsAny::Undefined(_) => "undefined",
+            JsAny::Null(_) => "null",
+            JsAny::Boolean(_) => "boolean",
+            JsAny::BigInt(_) => "bigint",
+        }
+    }
+
     #[inline]
-    #[allow(dead_code)]
     pub(crate) fn env(&self) -> Env {
         match self {
             JsAny::String(s) => s.value.env,
@@ -187,6 +212,43 @@ impl<'e> JsAny<'e> {
         }
     }
 
+    /// Converts the value to any Rust type implementing `Deserialize`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[derive(Deserialize)]
+    /// struct Config {
+    ///     retries: u32
+    /// }
+    ///
+    /// #[pinar]
+    /// fn my_func(value: JsAny) -> JsResult<()> {
+    ///     let config: Config = value.deserialize()?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error with the location of the mismatch in the value (e.g: `.options.retries`)
+    #[cfg(feature = "pinar-serde")]
+    pub fn deserialize<T: serde::de::DeserializeOwned>(&self) -> JsResult<T> {
+        crate::pinar_serde::de::from_any(self.env(), self.clone()).map_err(Into::into)
+    }
+
+    /// Converts the value to a Rust type borrowing its strings from `scratch`.
+    ///
+    /// See [`from_any_in`].
+    ///
+    /// [`from_any_in`]: ./fn.from_any_in.html
+    #[cfg(feature = "pinar-serde")]
+    pub fn deserialize_in<'de, T>(&self, scratch: &'de mut crate::Scratch) -> JsResult<T>
+    where
+        T: serde::Deserialize<'de>
+    {
+        crate::pinar_serde::de::from_any_in(self.env(), self.clone(), scratch).map_err(Into::into)
+    }
+
     impl_jsany!(
         RUST_TYPES:
         (as_string, String, String),
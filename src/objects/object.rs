@@ -3,7 +3,10 @@ use std::marker::PhantomData;
 use crate::*;
 use crate::prelude::*;
 use super::*;
-use crate::error::JsClassError;
+#[cfg(feature = "napi8")]
+use crate::type_tag::{tag_value, has_tag};
+use crate::error::JsAnyError;
+use super::iterable::ForAwait;
 
 /// A Javascript object
 pub struct JsObject<'e> {
@@ -214,22 +217,112 @@ impl<'e> JsObject<'e> {
         Ok(())
     }
 
+    /// Returns an iterator over the values of a javascript iterable
+    /// (Set, Map, generator, ..), using its `[Symbol.iterator]` method.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[pinar]
+    /// fn my_func(set: JsObject) -> JsResult<()> {
+    ///     for value in set.iter_js()? {
+    ///         let value: JsAny = value?;
+    ///         // ...
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the object is not iterable
+    pub fn iter_js(&self) -> JsResult<JsObjectIterator<'e>> {
+        let env = self.env();
+        let iterator = match self.get(env.well_known_symbol("iterator")?)? {
+            JsAny::Function(method) => method.call_with_this(self.get_value(), ())?,
+            _ => return Err(JsAnyError::NotIterable.into())
+        };
+        JsObjectIterator::new(iterator.as_jsobject()?)
+    }
+
+    /// Iterates over a javascript async iterable, similar to a `for await...of`.
+    ///
+    /// `fun` is called with each value, once the promise returned by the
+    /// iterator `next()` is fulfilled.  
+    /// Returns a promise, fulfilled at the end of the iteration, or rejected
+    /// with the first error (of the iterator or of `fun`).
+    ///
+    /// Synchronous iterables are accepted too.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[pinar]
+    /// fn count(stream: JsObject) -> JsResult<JsObject> {
+    ///     let mut count = 0;
+    ///     stream.for_await(move |value| {
+    ///         count += 1;
+    ///         Ok(())
+    ///     })
+    /// }
+    /// ```
+    ///
+    /// # Errors
+    /// Returns an error if the object is not iterable
+    pub fn for_await<F>(&self, fun: F) -> JsResult<JsObject<'e>>
+    where
+        F: FnMut(JsAny) -> JsResult<()> + 'static
+    {
+        let env = self.env();
+        let method = match self.get(env.well_known_symbol("asyncIterator")?)? {
+            JsAny::Function(method) => method,
+            _ => match self.get(env.well_known_symbol("iterator")?)? {
+                JsAny::Function(method) => method,
+                _ => return Err(JsAnyError::NotIterable.into())
+            }
+        };
+        let iterator = method.call_with_this(self.get_value(), ())?.as_jsobject()?;
+
+        let promise = ForAwait::start(env, iterator, fun)?;
+        Ok(JsObject::from(promise))
+    }
+
+    /// Tags the object, see [`TypeTag`].
+    ///
+    /// An object can be tagged only once.  
+    /// Class instances created by pinar are already tagged.  
+    /// Requires the `napi8` feature.
+    ///
+    /// [`TypeTag`]: ./struct.TypeTag.html
+    #[cfg(feature = "napi8")]
+    pub fn type_tag(&self, tag: TypeTag) -> JsResult<()> {
+        tag_value(self.get_value(), tag)
+    }
+
+    /// Returns true if the object has been tagged with `tag`
+    #[cfg(feature = "napi8")]
+    pub fn has_type_tag(&self, tag: TypeTag) -> JsResult<bool> {
+        has_tag(self.get_value(), tag)
+    }
+
     /// Retrieves a native instance that was previously wrapped in
-    /// a JavaScript object using napi_wrap()
-    pub(crate) fn napi_unwrap<T>(&self) -> JsResult<*mut T> {
+    /// a JavaScript object using napi_wrap().
+    ///
+    /// Returns `None` when the object has no native instance
+    pub(crate) fn napi_unwrap<T>(&self) -> Option<*mut T> {
         let mut obj: *mut T = std::ptr::null_mut();
 
-        napi_call!(napi_unwrap(
-            self.value.env(),
-            self.get_value().value,
-            &mut obj as *mut *mut T as *mut *mut std::ffi::c_void
-        ))?;
-
-        if obj.is_null() {
-            return Err(JsClassError::Unwrap.into())
+        let status = unsafe {
+            napi_unwrap(
+                self.value.env(),
+                self.get_value().value,
+                &mut obj as *mut *mut T as *mut *mut std::ffi::c_void
+            )
+        };
+
+        match status {
+            napi_status::napi_ok if !obj.is_null() => Some(obj),
+            _ => None
         }
-
-        Ok(obj)
     }
 }
 
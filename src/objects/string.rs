@@ -1,4 +1,5 @@
 
+use std::cell::RefCell;
 use std::marker::PhantomData;
 use std::os::raw::c_char;
 use napi_sys::*;
@@ -30,6 +31,140 @@ impl<'e> JsString<'e> {
     pub fn is_empty(&self) -> JsResult<bool> {
         Ok(self.len()? == 0)
     }
+
+    /// Calls `fun` with the content of the string.
+    ///
+    /// The string is copied in a buffer of the thread, reused by the next
+    /// calls: it grows to the size of the largest string read.  
+    /// A new buffer is allocated only when `fun` reads a string itself.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// #[pinar]
+    /// fn count_words(string: JsString) -> JsResult<usize> {
+    ///     string.with_str(|s| s.split_whitespace().count())
+    /// }
+    /// ```
+    pub fn with_str<F, R>(&self, fun: F) -> JsResult<R>
+    where
+        F: FnOnce(&str) -> R
+    {
+        self.read(|bytes| fun(bytes.as_str()))
+    }
+
+    /// Reads the string and calls `fun` with its bytes.
+    ///
+    /// The string is read in the scratch buffer, with a single napi call when
+    /// it fits. Otherwise the buffer grows to the length of the string.  
+    /// When the scratch buffer is in use (re-entrant call), the string is
+    /// read in a buffer of its exact size.
+    pub(crate) fn read<F, R>(&self, fun: F) -> JsResult<R>
+    where
+        F: FnOnce(Utf8Bytes) -> R
+    {
+        with_scratch(|scratch| match scratch {
+            Some(buffer) => {
+                if !self.read_with_capacity(buffer)? {
+                    buffer.clear();
+                    buffer.reserve(self.len()? + 1); // + '\0'
+                    self.read_with_capacity(buffer)?;
+                }
+                Ok(fun(Utf8Bytes::Scratch(buffer)))
+            }
+            None => {
+                let mut buffer = Vec::with_capacity(self.len()? + 1); // + '\0'
+                self.read_with_capacity(&mut buffer)?;
+                Ok(fun(Utf8Bytes::Owned(buffer)))
+            }
+        })
+    }
+
+    /// Reads the string in the capacity of the buffer.
+    /// Returns false if the string might be truncated.
+    fn read_with_capacity(&self, buffer: &mut Vec<u8>) -> JsResult<bool> {
+        let capacity = buffer.capacity();
+        let mut written = 0usize;
+
+        napi_call!(napi_get_value_string_utf8(
+            self.value.env(),
+            self.value.get(),
+            buffer.as_mut_ptr() as *mut c_char,
+            capacity,
+            &mut written as *mut usize
+        ))?;
+
+        unsafe {
+            buffer.set_len(written);
+        }
+
+        // napi doesn't write partial characters: a truncated string
+        // leaves less than a character of room, with the '\0'
+        Ok(written + MAX_UTF8_LEN < capacity)
+    }
+}
+
+/// The utf8 content of a string, read by [`JsString::read`]
+///
+/// napi replaces the invalid sequences, the bytes are valid utf8.
+pub(crate) enum Utf8Bytes<'a> {
+    /// In the scratch buffer
+    Scratch(&'a [u8]),
+    /// In its own buffer, read while the scratch buffer was in use
+    Owned(Vec<u8>),
+}
+
+impl<'a> Utf8Bytes<'a> {
+    pub(crate) fn as_str(&self) -> &str {
+        let bytes = match self {
+            Utf8Bytes::Scratch(bytes) => bytes,
+            Utf8Bytes::Owned(bytes) => bytes.as_slice(),
+        };
+        unsafe { std::str::from_utf8_unchecked(bytes) }
+    }
+
+    /// Converts to a `String`, copying the bytes of the scratch buffer
+    pub(crate) fn into_string(self) -> String {
+        match self {
+            Utf8Bytes::Owned(bytes) => unsafe { String::from_utf8_unchecked(bytes) },
+            scratch => scratch.as_str().to_owned(),
+        }
+    }
+}
+
+/// Length in bytes of the longest utf8 character
+const MAX_UTF8_LEN: usize = 4;
+
+/// Initial capacity of the scratch buffer
+const SCRATCH_CAPACITY: usize = 64 * 1024;
+
+/// The scratch buffer is shrunk to `SCRATCH_CAPACITY` when it grows
+/// larger than this, to not keep a huge string alive
+const SCRATCH_MAX_CAPACITY: usize = 16 * 1024 * 1024;
+
+thread_local! {
+    static SCRATCH: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls `fun` with the scratch buffer of the thread, `None` when it's
+/// already borrowed.
+fn with_scratch<F, R>(fun: F) -> R
+where
+    F: FnOnce(Option<&mut Vec<u8>>) -> R
+{
+    SCRATCH.with(|scratch| match scratch.try_borrow_mut() {
+        Ok(mut buffer) => {
+            buffer.clear();
+            buffer.reserve(SCRATCH_CAPACITY);
+            let result = fun(Some(&mut buffer));
+            if buffer.capacity() > SCRATCH_MAX_CAPACITY {
+                buffer.clear();
+                buffer.shrink_to(SCRATCH_CAPACITY);
+            }
+            result
+        }
+        _ => fun(None)
+    })
 }
 
 impl<'e> std::fmt::Display for JsString<'e> {
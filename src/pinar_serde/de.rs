@@ -1,45 +1,69 @@
 
 use serde;
 use serde::de::Visitor;
-use serde::de::{DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected,
+use serde::de::{Deserialize, DeserializeOwned, DeserializeSeed, EnumAccess, MapAccess, SeqAccess, Unexpected,
                 VariantAccess};
 use serde::forward_to_deserialize_any;
 
+use napi_sys::*;
+
+use crate::error::JsConversionError;
+use crate::to_rust::check_integer;
 use crate::prelude::*;
+use super::handle::{visit_handle, HANDLE_MARKER};
+use std::cell::RefCell;
 use std::fmt;
 use std::collections::VecDeque;
 
 #[derive(Debug)]
 pub struct DeserializeError {
-    msg: String
+    msg: String,
+    /// Location of the error in the javascript value.  
+    /// e.g: `.options.retries[2]`
+    path: String
 }
 
 impl crate::error::JsError for DeserializeError {
     fn get_msg(&self) -> String {
-        format!("DeserializeError {}", self.msg)
+        format!("DeserializeError {}", self)
     }
 }
 
 impl DeserializeError {
     fn new<M: AsRef<str>>(msg: M) -> DeserializeError {
         let msg = msg.as_ref();
-        DeserializeError { msg: msg.to_string() }
+        DeserializeError { msg: msg.to_string(), path: String::new() }
+    }
+
+    /// Prepends a segment to the path of the error
+    fn prepend(mut self, segment: impl std::fmt::Display) -> DeserializeError {
+        self.path.insert_str(0, &segment.to_string());
+        self
+    }
+
+    /// Prepends the array index to the path of the error
+    fn at_index(self, index: u32) -> DeserializeError {
+        self.prepend(format_args!("[{}]", index))
+    }
+
+    /// Prepends the object key to the path of the error
+    fn at_key(self, key: &JsAny) -> DeserializeError {
+        match key.as_string() {
+            Ok(key) => self.prepend(format_args!(".{}", key)),
+            _ => self.prepend(format_args!(".<{}>", key.type_name()))
+        }
     }
 }
 
 impl From<crate::Error> for DeserializeError {
     fn from(o: crate::Error) -> DeserializeError {
-        DeserializeError {
-            msg: format!("{:?}", o)
-        }
+        DeserializeError::new(o.as_js_error().get_msg())
     }
 }
 
 impl From<Status> for DeserializeError {
     fn from(o: Status) -> DeserializeError {
-        DeserializeError {
-            msg: format!("{:?}", o)
-        }
+        DeserializeError::new(format!("{}", o))
     }
 }
 
@@ -47,53 +71,152 @@ type Result<T> = std::result::Result<T, DeserializeError>;
 
 impl serde::de::Error for DeserializeError {
     fn custom<T: fmt::Display>(msg: T) -> Self {
-        Self { msg: msg.to_string() }
+        DeserializeError::new(msg.to_string())
     }
 }
 
 impl fmt::Display for DeserializeError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str(std::error::Error::description(self))
+        match self.path.as_str() {
+            "" => formatter.write_str(&self.msg),
+            path => write!(formatter, "{}: {}", path, self.msg)
+        }
     }
 }
 
-impl std::error::Error for DeserializeError {
-    fn description(&self) -> &str {
-        self.msg.as_str()
-    }
-}
+impl std::error::Error for DeserializeError {}
 
 pub fn from_any<T>(env: Env, any: JsAny) -> Result<T>
 where
     T: DeserializeOwned + ?Sized,
 {
-    let de: Deserializer = Deserializer::new(env, any);
+    let de: Deserializer = Deserializer::new(env, None, any);
     T::deserialize(de)
 }
 
+/// Deserializes a value borrowing its strings (`&str`, `Cow<str>`, ..) from
+/// `scratch`.
+///
+/// The strings of the value are copied one after the other in the buffers of
+/// `scratch`, which are cleared and reused by the next call.
+///
+/// # Example
+///
+/// ```
+/// #[derive(Deserialize)]
+/// struct Message<'a> {
+///     kind: &'a str,
+///     body: &'a str
+/// }
+///
+/// #[pinar]
+/// fn count_bytes(messages: Vec<JsAny>) -> JsResult<usize> {
+///     let mut scratch = Scratch::new();
+///     let mut total = 0;
+///     for message in messages {
+///         let message: Message = message.deserialize_in(&mut scratch)?;
+///         total += message.body.len();
+///     }
+///     Ok(total)
+/// }
+/// ```
+pub fn from_any_in<'de, T>(env: Env, any: JsAny, scratch: &'de mut Scratch) -> Result<T>
+where
+    T: Deserialize<'de>,
+{
+    scratch.clear();
+    let de: Deserializer = Deserializer::new(env, Some(scratch), any);
+    T::deserialize(de)
+}
+
+/// Buffers storing the strings borrowed by the values deserialized with
+/// [`from_any_in`].
+///
+/// [`from_any_in`]: ./fn.from_any_in.html
+pub struct Scratch {
+    /// The strings are appended to the last chunk.
+    /// A chunk never grows: the strings already stored never move.
+    chunks: RefCell<Vec<Vec<u8>>>
+}
+
+/// Capacity of a new chunk of `Scratch`
+const CHUNK_CAPACITY: usize = 16 * 1024;
+
+impl Scratch {
+    /// Creates an empty `Scratch`, its buffers are allocated on use
+    pub fn new() -> Scratch {
+        Scratch { chunks: RefCell::new(Vec::new()) }
+    }
+
+    /// Removes the strings, keeping the largest chunk
+    fn clear(&mut self) {
+        let chunks = self.chunks.get_mut();
+        if let Some(mut largest) = chunks.drain(..).max_by_key(Vec::capacity) {
+            largest.clear();
+            chunks.push(largest);
+        }
+    }
+
+    /// Copies the string in the chunks
+    fn push(&self, string: &JsString) -> Result<&str> {
+        let bytes = string.read(|bytes| self.push_str(bytes.as_str()))?;
+        // The bytes come from a str
+        Ok(unsafe { std::str::from_utf8_unchecked(bytes) })
+    }
+
+    fn push_str(&self, string: &str) -> &[u8] {
+        let len = string.len();
+        if len == 0 {
+            return &[];
+        }
+
+        let mut chunks = self.chunks.borrow_mut();
+        let fits = matches!(chunks.last(), Some(chunk) if chunk.capacity() - chunk.len() >= len);
+        if !fits {
+            chunks.push(Vec::with_capacity(len.max(CHUNK_CAPACITY)));
+        }
+
+        let chunk = chunks.last_mut().unwrap();
+        let start = chunk.len();
+        // Within the capacity, the chunk is not reallocated
+        chunk.extend_from_slice(string.as_bytes());
+
+        // The chunk is neither reallocated nor cleared while `self` is borrowed
+        unsafe { std::slice::from_raw_parts(chunk.as_ptr().add(start), len) }
+    }
+}
+
+impl Default for Scratch {
+    fn default() -> Scratch {
+        Scratch::new()
+    }
+}
+
 pub fn from_value<T>(env: Env, value: Value) -> Result<T>
 where
     T: DeserializeOwned + ?Sized,
 {
-    let de: Deserializer = Deserializer::new(env, JsAny::from(value)?);
+    let de: Deserializer = Deserializer::new(env, None, JsAny::from(value)?);
     T::deserialize(de)
 }
 
 #[doc(hidden)]
-pub struct Deserializer<'e> {
+pub struct Deserializer<'e, 'de> {
     env: Env,
+    /// Storage of the borrowed strings, see `from_any_in`
+    scratch: Option<&'de Scratch>,
     input: JsAny<'e>
 }
 
 #[doc(hidden)]
-impl<'e> Deserializer<'e> {
-    fn new(env: Env, input: JsAny<'e>) -> Self {
-        Deserializer { env, input }
+impl<'e, 'de> Deserializer<'e, 'de> {
+    fn new(env: Env, scratch: Option<&'de Scratch>, input: JsAny<'e>) -> Self {
+        Deserializer { env, scratch, input }
     }
 }
 
 #[doc(hidden)]
-impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
+impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e, 'de> {
     type Error = DeserializeError;
 
     fn deserialize_any<V>(self, visitor: V) -> Result<V::Value>
@@ -101,25 +224,28 @@ impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
         V: Visitor<'de>,
     {
         match self.input {
-            JsAny::String(s) => visitor.visit_string(s.to_rust()?),
+            JsAny::String(s) => match self.scratch {
+                Some(scratch) => visitor.visit_borrowed_str(scratch.push(&s)?),
+                // The visitor copies the string only if it needs to own it
+                _ => s.with_str(|s| visitor.visit_str(s))?
+            },
             JsAny::Undefined(_) => visitor.visit_unit(),
             JsAny::Null(_) => visitor.visit_unit(),
             JsAny::Boolean(b) => visitor.visit_bool(b.to_rust()?),
             JsAny::Object(o) => {
-                let mut deserializer = JsObjectAccess::new(self.env, o)?;
+                let mut deserializer = JsObjectAccess::new(self.env, self.scratch, o)?;
                 visitor.visit_map(&mut deserializer)
             },
             JsAny::Array(a) => {
-                let mut deserializer = JsArrayAccess::new(self.env, a);
+                let mut deserializer = JsArrayAccess::new(self.env, self.scratch, a)?;
                 visitor.visit_seq(&mut deserializer)
             },
-            JsAny::Number(n) => {
-                visitor.visit_i64(n.to_rust()?)
-            },
-            JsAny::Symbol(_) => unimplemented!(),
-            JsAny::External(_) => unimplemented!(),
-            JsAny::Function(_) => unimplemented!(),
-            JsAny::BigInt(_) => unimplemented!(),
+            JsAny::Number(n) => visit_number(n.to_rust()?, visitor),
+            JsAny::BigInt(b) => visit_bigint(b.get_value(), visitor),
+            other => Err(serde::de::Error::invalid_type(
+                Unexpected::Other(other.type_name()),
+                &visitor
+            )),
         }
     }
 
@@ -133,6 +259,11 @@ impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
         }
     }
 
+    /// Externally tagged enums: a string for unit variants, or an object
+    /// with a single key, the variant name.
+    ///
+    /// Internally tagged, adjacently tagged and untagged enums don't use
+    /// this method, serde buffers the value with `deserialize_any`.
     fn deserialize_enum<V>(
         self,
         _name: &'static str,
@@ -144,11 +275,10 @@ impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
     {
         match self.input {
             JsAny::String(s) => {
-                visitor.visit_enum(JsEnumAccess::new(self.env, s.to_rust().unwrap(), None))
+                visitor.visit_enum(JsEnumAccess::new(self.env, self.scratch, s.to_rust()?, None))
             },
             JsAny::Object(o) => {
-                let props = o.get_property_names()
-                             .map_err(|e| DeserializeError::new(format!("{:?}", e)))?;
+                let props = o.get_property_names()?;
 
                 if props.len() != 1 {
                     return Err(DeserializeError::new(
@@ -156,29 +286,53 @@ impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
                     ));
                 }
                 let key = &props[0];
-                let value = o.get(key.as_str())
-                             .map_err(|e| DeserializeError::new(format!("{:?}", e)))?;
+                let value = o.get(key.as_str())?;
 
-                visitor.visit_enum(JsEnumAccess::new(self.env, key.to_string(), Some(value)))
+                visitor.visit_enum(JsEnumAccess::new(self.env, self.scratch, key.to_string(), Some(value)))
+                       .map_err(|e| e.prepend(format_args!(".{}", key)))
             },
-            _ => {
-                unimplemented!()
-            }
+            other => Err(serde::de::Error::invalid_type(
+                Unexpected::Other(other.type_name()),
+                &"a string or an object with a single key",
+            ))
         }
     }
 
-    fn deserialize_bytes<V>(self, _visitor: V) -> Result<V::Value>
+    /// Accepts a `Buffer` or an array of bytes
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        self.deserialize_byte_buf(visitor)
     }
 
-    fn deserialize_byte_buf<V>(self, _visitor: V) -> Result<V::Value>
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value>
     where
         V: Visitor<'de>,
     {
-        unimplemented!()
+        match self.input {
+            JsAny::Object(ref o) if is_buffer(o.get_value())? => {
+                visitor.visit_byte_buf(buffer_to_vec(o.get_value())?)
+            },
+            JsAny::Array(a) => {
+                let bytes: Vec<u8> = serde::de::Deserialize::deserialize(Deserializer::new(self.env, self.scratch, JsAny::Array(a)))?;
+                visitor.visit_byte_buf(bytes)
+            },
+            other => Err(serde::de::Error::invalid_type(
+                Unexpected::Other(other.type_name()),
+                &"a buffer or an array of bytes"
+            ))
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match name {
+            HANDLE_MARKER => visit_handle(self.input.get_value(), visitor),
+            _ => visitor.visit_newtype_struct(self)
+        }
     }
 
     fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
@@ -188,36 +342,163 @@ impl<'e, 'de> serde::de::Deserializer<'de> for Deserializer<'e> {
         visitor.visit_unit()
     }
 
+    fn deserialize_i64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.input {
+            JsAny::Number(ref n) => {
+                let n = to_integer(n.to_rust()?, "i64", 64, true)?;
+                visitor.visit_i64(n as i64)
+            },
+            _ => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_u64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.input {
+            JsAny::Number(ref n) => {
+                let n = to_integer(n.to_rust()?, "u64", 64, false)?;
+                visitor.visit_u64(n as u64)
+            },
+            _ => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_f32<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.input {
+            JsAny::Number(ref n) => {
+                let n: f64 = n.to_rust()?;
+                // NaN and infinities are kept as they are
+                if n.is_finite() && n.abs() > f64::from(f32::MAX) {
+                    return Err(DeserializeError::new(
+                        JsConversionError::OutOfRange(n.to_string(), "f32").to_string()
+                    ));
+                }
+                visitor.visit_f32(n as f32)
+            },
+            _ => self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_f64<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: Visitor<'de>,
+    {
+        match self.input {
+            JsAny::Number(ref n) => visitor.visit_f64(n.to_rust()?),
+            _ => self.deserialize_any(visitor)
+        }
+    }
+
     forward_to_deserialize_any! {
-        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string
+        bool i8 i16 i32 u8 u16 u32 char str string
         unit unit_struct seq tuple tuple_struct map struct identifier
-        newtype_struct
     }
 }
 
+/// -2^63, the lowest `i64`
+const I64_START: f64 = -9_223_372_036_854_775_808.0;
+/// 2^64, one above the greatest `u64`
+const U64_END: f64 = 18_446_744_073_709_551_616.0;
+
+/// Dispatches a javascript number to the visitor.
+///
+/// Integers are visited as `u64` (or `i64` when negative), other numbers,
+/// including NaN and infinities, as `f64`.
+fn visit_number<'de, V>(n: f64, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    if n.is_finite() && n.fract() == 0.0 {
+        if (0.0..U64_END).contains(&n) {
+            return visitor.visit_u64(n as u64);
+        }
+        if (I64_START..0.0).contains(&n) {
+            return visitor.visit_i64(n as i64);
+        }
+    }
+    visitor.visit_f64(n)
+}
+
+/// Dispatches a javascript BigInt to the visitor, as `i64` or `u64`
+fn visit_bigint<'de, V>(value: Value, visitor: V) -> Result<V::Value>
+where
+    V: Visitor<'de>,
+{
+    let mut lossless = false;
+
+    let mut signed = 0i64;
+    napi_call!(napi_get_value_bigint_int64(value.env(), value.get(), &mut signed, &mut lossless))?;
+    if lossless {
+        return visitor.visit_i64(signed);
+    }
+
+    let mut unsigned = 0u64;
+    napi_call!(napi_get_value_bigint_uint64(value.env(), value.get(), &mut unsigned, &mut lossless))?;
+    if lossless {
+        return visitor.visit_u64(unsigned);
+    }
+
+    Err(DeserializeError::new("bigint is out of range for i64 and u64"))
+}
+
+fn is_buffer(value: Value) -> Result<bool> {
+    let mut result = false;
+    napi_call!(napi_is_buffer(value.env(), value.get(), &mut result))?;
+    Ok(result)
+}
+
+/// Copies the content of a `Buffer`
+fn buffer_to_vec(value: Value) -> Result<Vec<u8>> {
+    let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+    let mut length = 0usize;
+
+    napi_call!(napi_get_buffer_info(value.env(), value.get(), &mut data, &mut length))?;
+
+    if data.is_null() || length == 0 {
+        return Ok(Vec::new());
+    }
+    let bytes = unsafe { std::slice::from_raw_parts(data as *const u8, length) };
+    Ok(bytes.to_vec())
+}
+
+/// Checks that the number is an integer in the range of the type `ty`
+fn to_integer(n: f64, ty: &'static str, bits: u32, signed: bool) -> Result<f64> {
+    check_integer(n, ty, bits, signed).map_err(|e| DeserializeError::new(e.to_string()))
+}
+
 #[doc(hidden)]
-struct JsArrayAccess<'e> {
+struct JsArrayAccess<'e, 'de> {
     env: Env,
+    scratch: Option<&'de Scratch>,
     array: JsArray<'e>,
     index: u32,
     length: u32,
 }
 
 #[doc(hidden)]
-impl<'e> JsArrayAccess<'e> {
-    fn new(env: Env, array: JsArray<'e>) -> Self {
-        let length = array.len().unwrap() as u32;
-        JsArrayAccess {
+impl<'e, 'de> JsArrayAccess<'e, 'de> {
+    fn new(env: Env, scratch: Option<&'de Scratch>, array: JsArray<'e>) -> Result<Self> {
+        let length = array.len()? as u32;
+        Ok(JsArrayAccess {
             env,
+            scratch,
             array,
             index: 0,
             length
-        }
+        })
     }
 }
 
 #[doc(hidden)]
-impl<'e, 'de> SeqAccess<'de> for JsArrayAccess<'e> {
+impl<'e, 'de> SeqAccess<'de> for JsArrayAccess<'e, 'de> {
     type Error = DeserializeError;
 
     fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>>
@@ -227,11 +508,14 @@ impl<'e, 'de> SeqAccess<'de> for JsArrayAccess<'e> {
         if self.index >= self.length {
             return Ok(None);
         }
-        let value = self.array.get(self.index)?;
+        let index = self.index;
+        let value = self.array.get(index)?;
         self.index += 1;
 
-        let de = Deserializer::new(self.env, value);
-        seed.deserialize(de).map(Some)
+        let de = Deserializer::new(self.env, self.scratch, value);
+        seed.deserialize(de)
+            .map(Some)
+            .map_err(|e| e.at_index(index))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -240,19 +524,21 @@ impl<'e, 'de> SeqAccess<'de> for JsArrayAccess<'e> {
 }
 
 #[doc(hidden)]
-struct JsObjectAccess<'e> {
+struct JsObjectAccess<'e, 'de> {
     env: Env,
+    scratch: Option<&'de Scratch>,
     object: JsObject<'e>,
     props: VecDeque<JsAny<'e>>,
 }
 
 #[doc(hidden)]
-impl<'e> JsObjectAccess<'e> {
-    fn new(env: Env, object: JsObject<'e>) -> Result<Self> {
-        let props = VecDeque::from(object.get_property_names_any().unwrap());
+impl<'e, 'de> JsObjectAccess<'e, 'de> {
+    fn new(env: Env, scratch: Option<&'de Scratch>, object: JsObject<'e>) -> Result<Self> {
+        let props = VecDeque::from(object.get_property_names_any()?);
 
         Ok(JsObjectAccess {
             env,
+            scratch,
             object,
             props,
         })
@@ -260,7 +546,7 @@ impl<'e> JsObjectAccess<'e> {
 }
 
 #[doc(hidden)]
-impl<'e, 'de> MapAccess<'de> for JsObjectAccess<'e> {
+impl<'e, 'de> MapAccess<'de> for JsObjectAccess<'e, 'de> {
     type Error = DeserializeError;
 
     fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
@@ -271,23 +557,23 @@ impl<'e, 'de> MapAccess<'de> for JsObjectAccess<'e> {
             return Ok(None)
         }
 
-        let prop = self.props.front().map(|v| v.clone()).unwrap();
-        let de = Deserializer::new(self.env, prop);
-        seed.deserialize(de).map(Some)
+        let prop = self.props[0].clone();
+        let de = Deserializer::new(self.env, self.scratch, prop.clone());
+        seed.deserialize(de).map(Some).map_err(|e| e.at_key(&prop))
     }
 
     fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
     where
         V: DeserializeSeed<'de>,
     {
-        if self.props.is_empty() {
-            panic!("Fetching value with empty props");
-        }
-        let key = self.props.pop_front().unwrap();
-        let value = self.object.get(key.get_value()).unwrap();
-
-        let de = Deserializer::new(self.env, value);
-        seed.deserialize(de)
+        let key = match self.props.pop_front() {
+            Some(key) => key,
+            _ => return Err(DeserializeError::new("value requested without a key"))
+        };
+        let value = self.object.get(key.get_value()).map_err(|e| DeserializeError::from(e).at_key(&key))?;
+
+        let de = Deserializer::new(self.env, self.scratch, value);
+        seed.deserialize(de).map_err(|e| e.at_key(&key))
     }
 
     fn next_entry_seed<K, V>(&mut self, kseed: K, vseed: V) -> Result<Option<(K::Value, V::Value)>>
@@ -295,19 +581,19 @@ impl<'e, 'de> MapAccess<'de> for JsObjectAccess<'e> {
         K: DeserializeSeed<'de>,
         V: DeserializeSeed<'de>,
     {
-        if self.props.is_empty() {
-            return Ok(None);
-        }
-        let key = self.props.pop_front().unwrap();
-        let value = self.object.get(key.get_value()).unwrap();
+        let key = match self.props.pop_front() {
+            Some(key) => key,
+            _ => return Ok(None)
+        };
+        let value = self.object.get(key.get_value()).map_err(|e| DeserializeError::from(e).at_key(&key))?;
 
-        let de = Deserializer::new(self.env, key);
-        let key = kseed.deserialize(de)?;
+        let de = Deserializer::new(self.env, self.scratch, key.clone());
+        let key_value = kseed.deserialize(de).map_err(|e| e.at_key(&key))?;
 
-        let de = Deserializer::new(self.env, value);
-        let value = vseed.deserialize(de)?;
+        let de = Deserializer::new(self.env, self.scratch, value);
+        let value = vseed.deserialize(de).map_err(|e| e.at_key(&key))?;
 
-        Ok(Some((key, value)))
+        Ok(Some((key_value, value)))
     }
 
     fn size_hint(&self) -> Option<usize> {
@@ -316,17 +602,19 @@ impl<'e, 'de> MapAccess<'de> for JsObjectAccess<'e> {
 }
 
 #[doc(hidden)]
-struct JsEnumAccess<'e> {
+struct JsEnumAccess<'e, 'de> {
     env: Env,
+    scratch: Option<&'de Scratch>,
     variant: String,
     value: Option<JsAny<'e>>,
 }
 
 #[doc(hidden)]
-impl<'e> JsEnumAccess<'e> {
-    fn new(env: Env, key: String, value: Option<JsAny<'e>>) -> Self {
+impl<'e, 'de> JsEnumAccess<'e, 'de> {
+    fn new(env: Env, scratch: Option<&'de Scratch>, key: String, value: Option<JsAny<'e>>) -> Self {
         JsEnumAccess {
             env,
+            scratch,
             variant: key,
             value,
         }
@@ -334,9 +622,9 @@ impl<'e> JsEnumAccess<'e> {
 }
 
 #[doc(hidden)]
-impl<'e, 'de> EnumAccess<'de> for JsEnumAccess<'e> {
+impl<'e, 'de> EnumAccess<'de> for JsEnumAccess<'e, 'de> {
     type Error = DeserializeError;
-    type Variant = JsVariantAccess<'e>;
+    type Variant = JsVariantAccess<'e, 'de>;
 
     fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant)>
     where
@@ -344,32 +632,33 @@ impl<'e, 'de> EnumAccess<'de> for JsEnumAccess<'e> {
     {
         use serde::de::IntoDeserializer;
         let variant = self.variant.into_deserializer();
-        let variant_access = JsVariantAccess::new(self.env, self.value);
+        let variant_access = JsVariantAccess::new(self.env, self.scratch, self.value);
         seed.deserialize(variant).map(|v| (v, variant_access))
     }
 }
 
 #[doc(hidden)]
-struct JsVariantAccess<'e> {
+struct JsVariantAccess<'e, 'de> {
     env: Env,
+    scratch: Option<&'de Scratch>,
     value: Option<JsAny<'e>>,
 }
 
 #[doc(hidden)]
-impl<'e> JsVariantAccess<'e> {
-    fn new(env: Env, value: Option<JsAny<'e>>) -> Self {
-        JsVariantAccess { env, value }
+impl<'e, 'de> JsVariantAccess<'e, 'de> {
+    fn new(env: Env, scratch: Option<&'de Scratch>, value: Option<JsAny<'e>>) -> Self {
+        JsVariantAccess { env, scratch, value }
     }
 }
 
 #[doc(hidden)]
-impl<'e, 'de> VariantAccess<'de> for JsVariantAccess<'e> {
+impl<'e, 'de> VariantAccess<'de> for JsVariantAccess<'e, 'de> {
     type Error = DeserializeError;
 
     fn unit_variant(self) -> Result<()> {
         match self.value {
             Some(val) => {
-                let deserializer = Deserializer::new(self.env, val);
+                let deserializer = Deserializer::new(self.env, self.scratch, val);
                 serde::de::Deserialize::deserialize(deserializer)
             }
             None => Ok(()),
@@ -382,7 +671,7 @@ impl<'e, 'de> VariantAccess<'de> for JsVariantAccess<'e> {
     {
         match self.value {
             Some(val) => {
-                let deserializer = Deserializer::new(self.env, val);
+                let deserializer = Deserializer::new(self.env, self.scratch, val);
                 seed.deserialize(deserializer)
             }
             None => Err(serde::de::Error::invalid_type(
@@ -398,7 +687,7 @@ impl<'e, 'de> VariantAccess<'de> for JsVariantAccess<'e> {
     {
         match self.value {
             Some(JsAny::Array(a)) => {
-                let mut deserializer = JsArrayAccess::new(self.env, a);
+                let mut deserializer = JsArrayAccess::new(self.env, self.scratch, a)?;
                 visitor.visit_seq(&mut deserializer)
             },
             _ => Err(serde::de::Error::invalid_type(
@@ -414,7 +703,7 @@ impl<'e, 'de> VariantAccess<'de> for JsVariantAccess<'e> {
     {
         match self.value {
             Some(JsAny::Object(o)) => {
-                let mut deserializer = JsObjectAccess::new(self.env, o)?;
+                let mut deserializer = JsObjectAccess::new(self.env, self.scratch, o)?;
                 visitor.visit_map(&mut deserializer)
             },
             _ => Err(serde::de::Error::invalid_type(
@@ -0,0 +1,144 @@
+//! Javascript handles inside serde structs.
+//!
+//! `JsAny`, `JsFunction` and `JsExternal` implement `Serialize` and
+//! `Deserialize`, so they can be fields of a struct converted with serde:
+//!
+//! ```
+//! #[derive(Deserialize)]
+//! struct Options<'e> {
+//!     retries: u32,
+//!     on_error: JsFunction<'e>
+//! }
+//!
+//! #[pinar]
+//! fn my_func(value: JsAny) -> JsResult<()> {
+//!     let options: Options = value.deserialize()?;
+//!     options.on_error.call("oops")?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! The handles are passed through a newtype struct with a special name,
+//! recognized by the pinar serializer and deserializer.
+//! Other serializers and deserializers (`serde_json`, ..) return an error.
+
+use std::cell::Cell;
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, Unexpected, Visitor};
+use serde::ser::{self, Serialize, Serializer};
+
+use crate::prelude::*;
+
+/// Name of the newtype struct wrapping a handle
+pub(crate) const HANDLE_MARKER: &str = "$__pinar_private_handle";
+
+thread_local! {
+    /// The handle being (de)serialized
+    static HANDLE: Cell<Option<Value>> = const { Cell::new(None) };
+}
+
+fn set_handle(value: Option<Value>) {
+    HANDLE.with(|handle| handle.set(value));
+}
+
+/// Takes the handle being (de)serialized, if any
+pub(crate) fn take_handle() -> Option<Value> {
+    HANDLE.with(|handle| handle.take())
+}
+
+/// Passes the handle to the visitor of a `JsAny`, `JsFunction` or `JsExternal`
+pub(crate) fn visit_handle<'de, V, E>(value: Value, visitor: V) -> Result<V::Value, E>
+where
+    V: Visitor<'de>,
+    E: de::Error
+{
+    set_handle(Some(value));
+    let result = visitor.visit_unit();
+    set_handle(None);
+    result
+}
+
+struct HandleVisitor;
+
+impl<'de> Visitor<'de> for HandleVisitor {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a javascript value")
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        take_handle().ok_or_else(|| {
+            E::custom("javascript values can only be deserialized by pinar")
+        })
+    }
+}
+
+fn deserialize_handle<'e, 'de, D>(deserializer: D) -> Result<JsAny<'e>, D::Error>
+where
+    D: Deserializer<'de>
+{
+    let value = deserializer.deserialize_newtype_struct(HANDLE_MARKER, HandleVisitor)?;
+    JsAny::from(value).map_err(|e| de::Error::custom(e.as_js_error().get_msg()))
+}
+
+/// The content of the marker, reached only by other serializers
+struct Handle;
+
+impl Serialize for Handle {
+    fn serialize<S: Serializer>(&self, _serializer: S) -> Result<S::Ok, S::Error> {
+        set_handle(None);
+        Err(ser::Error::custom("javascript values can only be serialized by pinar"))
+    }
+}
+
+fn serialize_handle<S: Serializer>(value: Value, serializer: S) -> Result<S::Ok, S::Error> {
+    set_handle(Some(value));
+    let result = serializer.serialize_newtype_struct(HANDLE_MARKER, &Handle);
+    set_handle(None);
+    result
+}
+
+impl<'e, 'de> Deserialize<'de> for JsAny<'e> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<JsAny<'e>, D::Error> {
+        deserialize_handle(deserializer)
+    }
+}
+
+macro_rules! impl_handle {
+    (
+        $( $variant:ident, $type:ident, $expected:expr ),*
+    ) => {
+        $(
+            impl<'e, 'de> Deserialize<'de> for $type<'e> {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<$type<'e>, D::Error> {
+                    match deserialize_handle(deserializer)? {
+                        JsAny::$variant(value) => Ok(value),
+                        other => Err(de::Error::invalid_type(
+                            Unexpected::Other(other.type_name()),
+                            &$expected
+                        ))
+                    }
+                }
+            }
+
+            impl<'e> Serialize for $type<'e> {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serialize_handle(self.get_value(), serializer)
+                }
+            }
+        )*
+    }
+}
+
+impl_handle!(
+    Function, JsFunction, "a function",
+    External, JsExternal, "an external"
+);
+
+impl<'e> Serialize for JsAny<'e> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize_handle(self.get_value(), serializer)
+    }
+}
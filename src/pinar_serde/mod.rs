@@ -1,3 +1,4 @@
 
 pub mod ser;
 pub mod de;
+mod handle;
@@ -1,12 +1,14 @@
 
 use std::marker::PhantomData;
 use std::cell::Cell;
+use std::convert::TryFrom;
 use std::fmt;
 use crate::prelude::*;
 use crate::*;
 //use super::SerializedObject;
 
 use serde::ser::{self, Serialize};
+use super::handle::{take_handle, HANDLE_MARKER};
 
 #[derive(Debug)]
 pub struct SerializeError {
@@ -40,25 +42,169 @@ impl ser::Error for SerializeError {
 
 impl fmt::Display for SerializeError {
     fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str(std::error::Error::description(self))
+        formatter.write_str(&self.msg)
     }
 }
 
-impl std::error::Error for SerializeError {
-    fn description(&self) -> &str {
-        "status error"
-        //self.message.as_str()
-        //self.message.as_str()
+impl std::error::Error for SerializeError {}
+
+impl JsError for SerializeError {
+    fn get_code(&self) -> Option<String> {
+        Some("PINAR".to_owned())
+    }
+}
+
+/// How `None` is converted, see [`SerializerConfig::with_none`]
+///
+/// [`SerializerConfig::with_none`]: ./struct.SerializerConfig.html#method.with_none
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoneAs {
+    /// `null`, the default
+    Null,
+    /// `undefined`
+    Undefined,
+    /// The property is omitted from the object.
+    /// Outside of a struct, `None` is `undefined`
+    Omit,
+}
+
+/// How bytes are converted, see [`SerializerConfig::with_bytes`]
+///
+/// [`SerializerConfig::with_bytes`]: ./struct.SerializerConfig.html#method.with_bytes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytesAs {
+    /// An array of numbers, the default
+    Array,
+    /// A node `Buffer`
+    Buffer,
+    /// An `Uint8Array`
+    Uint8Array,
+}
+
+/// Options of [`serialize_to_js_with`].
+///
+/// The default configuration is the one used by [`serialize_to_js`].
+///
+/// Bytes are serialized only by types calling `serialize_bytes`, like
+/// [`serde_bytes`] wrappers. A `Vec<u8>` is a sequence.
+///
+/// # Limitation
+///
+/// Serde serializes a struct with a `#[serde(flatten)]` field as a map,
+/// which the serializer can't tell apart from a `HashMap`. For those structs:
+/// - [`with_map`] converts them to a `Map` instead of an object,
+/// - [`with_camel_case`] and `NoneAs::Omit` don't apply to their fields.
+///
+/// Their nested structs, including the flattened one, are not affected.
+///
+/// # Example
+///
+/// ```
+/// #[derive(Serialize)]
+/// struct User {
+///     first_name: String,
+///     nickname: Option<String>,
+///     id: u64
+/// }
+///
+/// #[pinar]
+/// fn get_user(env: Env) -> JsResult<JsAny> {
+///     let config = SerializerConfig::default()
+///         .with_camel_case()
+///         .with_none(NoneAs::Omit)
+///         .with_bigint();
+///
+///     let user = User { first_name: "Ferris".to_owned(), nickname: None, id: 1 };
+///     env.serialize_with(&user, &config)
+///     // { firstName: 'Ferris', id: 1n }
+/// }
+/// ```
+///
+/// [`serialize_to_js_with`]: ./fn.serialize_to_js_with.html
+/// [`serialize_to_js`]: ./fn.serialize_to_js.html
+/// [`with_map`]: #method.with_map
+/// [`with_camel_case`]: #method.with_camel_case
+/// [`serde_bytes`]: https://docs.rs/serde_bytes
+#[derive(Debug, Clone, Copy)]
+pub struct SerializerConfig {
+    none: NoneAs,
+    camel_case: bool,
+    map: bool,
+    bytes: BytesAs,
+    bigint: bool,
+}
+
+impl Default for SerializerConfig {
+    fn default() -> SerializerConfig {
+        SerializerConfig {
+            none: NoneAs::Null,
+            camel_case: false,
+            map: false,
+            bytes: BytesAs::Array,
+            bigint: false,
+        }
+    }
+}
+
+impl SerializerConfig {
+    /// Sets how `None` is converted, `null` by default
+    pub fn with_none(mut self, none: NoneAs) -> Self {
+        self.none = none;
+        self
+    }
+
+    /// Converts the struct field names to camelCase (`first_name` becomes `firstName`).
+    /// The keys of maps are not converted.
+    pub fn with_camel_case(mut self) -> Self {
+        self.camel_case = true;
+        self
+    }
+
+    /// Converts the maps (`HashMap`, `BTreeMap`, ..) to a javascript `Map`
+    /// instead of a plain object.
+    /// Structs are still converted to objects, except the ones with a
+    /// flattened field (see the limitation above).
+    pub fn with_map(mut self) -> Self {
+        self.map = true;
+        self
+    }
+
+    /// Sets how bytes are converted, an array of numbers by default
+    pub fn with_bytes(mut self, bytes: BytesAs) -> Self {
+        self.bytes = bytes;
+        self
+    }
+
+    /// Converts `i64`, `u64`, `i128` and `u128` to a `BigInt`.
+    ///
+    /// By default, they are converted to a number, which loses precision
+    /// above `Number.MAX_SAFE_INTEGER`.
+    pub fn with_bigint(mut self) -> Self {
+        self.bigint = true;
+        self
     }
 }
 
 #[inline]
 pub fn serialize_to_js<V>(env: Env, value: &V) -> Result<Value>
+where
+    V: Serialize + ?Sized,
+{
+    serialize_to_js_with(env, value, &SerializerConfig::default())
+}
+
+/// Converts a Rust value to javascript, with the configuration `config`.
+///
+/// See [`SerializerConfig`].
+///
+/// [`SerializerConfig`]: ./struct.SerializerConfig.html
+pub fn serialize_to_js_with<V>(env: Env, value: &V, config: &SerializerConfig) -> Result<Value>
 where
     V: Serialize + ?Sized,
 {
     let serializer = PinarSerializer {
         env,
+        config: *config,
         phantom: PhantomData
     };
     value.serialize(serializer)
@@ -68,21 +214,24 @@ where
 pub struct PinarSerializer<'e>
 {
     env: Env,
+    config: SerializerConfig,
     phantom: PhantomData<&'e ()>
 }
 
 #[doc(hidden)]
 pub struct PinarArraySer<'e> {
     env: Env,
+    config: SerializerConfig,
     current_index: Cell<usize>,
     array: JsArray<'e>,
     name_obj: Option<&'static str>
 }
 
 impl<'e> PinarArraySer<'e> {
-    fn new(env: Env, length: usize, name: Option<&'static str>) -> Result<PinarArraySer<'e>> {
+    fn new(env: Env, config: SerializerConfig, length: usize, name: Option<&'static str>) -> Result<PinarArraySer<'e>> {
         Ok(PinarArraySer {
             env,
+            config,
             current_index: Cell::new(0),
             array: env.array_with_capacity(length)?,
             name_obj: name
@@ -90,23 +239,125 @@ impl<'e> PinarArraySer<'e> {
     }
 }
 
+/// Where the entries of a map are written
+enum MapTarget<'e> {
+    Object(JsObject<'e>),
+    Map(JsMap<'e>),
+}
+
 #[doc(hidden)]
 pub struct PinarMapSer<'e> {
     env: Env,
-    obj: JsObject<'e>,
+    config: SerializerConfig,
+    obj: MapTarget<'e>,
     key: Option<Value>,
     name_obj: Option<&'static str>
 }
 
 impl<'e> PinarMapSer<'e> {
-    fn new(env: Env, name: Option<&'static str>) -> Result<PinarMapSer<'e>> {
+    fn new(env: Env, config: SerializerConfig, name: Option<&'static str>) -> Result<PinarMapSer<'e>> {
         Ok(PinarMapSer {
             env,
-            obj: env.object()?,
+            config,
+            obj: MapTarget::Object(env.object()?),
             key: None,
             name_obj: name
         })
     }
+
+    fn new_map(env: Env, config: SerializerConfig) -> Result<PinarMapSer<'e>> {
+        Ok(PinarMapSer {
+            env,
+            config,
+            obj: MapTarget::Map(JsMap::new(env)?),
+            key: None,
+            name_obj: None
+        })
+    }
+
+    fn set(&self, key: Value, value: Value) -> Result<()> {
+        match &self.obj {
+            MapTarget::Object(obj) => obj.set(key, value)?,
+            MapTarget::Map(map) => map.set(key, value)?,
+        };
+        Ok(())
+    }
+
+    fn to_js<T>(&self, value: &T) -> Result<Value>
+    where
+        T: ?Sized + Serialize,
+    {
+        serialize_to_js_with(self.env, value, &self.config)
+    }
+
+    /// Sets a field of a struct
+    fn set_field<T>(&self, key: &'static str, value: &T) -> Result<()>
+    where
+        T: ?Sized + Serialize,
+    {
+        let value = self.to_js(value)?;
+        if self.config.none == NoneAs::Omit && is_undefined(value)? {
+            return Ok(());
+        }
+        let key = match self.config.camel_case {
+            true => self.env.string(to_camel_case(key))?.get_value(),
+            _ => self.env.string(key)?.get_value()
+        };
+        self.set(key, value)
+    }
+
+    fn into_value(self) -> Value {
+        match self.obj {
+            MapTarget::Object(obj) => obj.get_value(),
+            MapTarget::Map(map) => map.get_value(),
+        }
+    }
+}
+
+fn is_undefined(value: Value) -> Result<bool> {
+    Ok(matches!(JsAny::from(value)?, JsAny::Undefined(_)))
+}
+
+/// Converts a snake_case name to camelCase
+fn to_camel_case(name: &str) -> String {
+    let mut result = String::with_capacity(name.len());
+    let mut upper = false;
+
+    for c in name.chars() {
+        // Leading underscores are kept
+        if c == '_' && result.chars().any(|c| c != '_') {
+            upper = true;
+        } else if upper {
+            result.extend(c.to_uppercase());
+            upper = false;
+        } else {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+impl<'e> PinarSerializer<'e> {
+    fn number(self, v: i64) -> Result<Value> {
+        Ok(self.env.number(v)?.get_value())
+    }
+
+    /// Creates a BigInt from its sign and absolute value
+    fn bigint(self, negative: bool, v: u128) -> Result<Value> {
+        let words = [v as u64, (v >> 64) as u64];
+        let mut result = Value::new(self.env);
+
+        napi_call!(napi_create_bigint_words(
+            self.env.env(),
+            negative as i32,
+            words.len(),
+            words.as_ptr(),
+            result.get_mut()
+        ))?;
+
+        Ok(result)
+    }
 }
 
 // impl<'e> PinarSerializer<'e> {
@@ -151,35 +402,56 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
     // will be serialized the same. Other formats, especially compact binary
     // formats, may need independent logic for the different sizes.
     fn serialize_i8(self, v: i8) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_i16(self, v: i16) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_i32(self, v: i32) -> Result<Self::Ok> {
-        self.serialize_i64(i64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_i64(self, v: i64) -> Result<Self::Ok> {
-        self.serialize_u64(v as u64)
+        match self.config.bigint {
+            true => self.serialize_i128(i128::from(v)),
+            _ => self.number(v)
+        }
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<Self::Ok> {
+        match self.config.bigint {
+            true => self.bigint(v < 0, v.unsigned_abs()),
+            _ => self.serialize_f64(v as f64)
+        }
     }
 
     fn serialize_u8(self, v: u8) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_u16(self, v: u16) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_u32(self, v: u32) -> Result<Self::Ok> {
-        self.serialize_u64(u64::from(v))
+        self.number(i64::from(v))
     }
 
     fn serialize_u64(self, v: u64) -> Result<Self::Ok> {
-        Ok(self.env.number(v as i64)?.get_value())
+        match (self.config.bigint, i64::try_from(v)) {
+            (true, _) => self.bigint(false, u128::from(v)),
+            (_, Ok(v)) => self.number(v),
+            _ => self.serialize_f64(v as f64)
+        }
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<Self::Ok> {
+        match self.config.bigint {
+            true => self.bigint(false, v),
+            _ => self.serialize_f64(v as f64)
+        }
     }
 
     fn serialize_f32(self, v: f32) -> Result<Self::Ok> {
@@ -203,10 +475,14 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         Ok(self.env.string(v)?.get_value())
     }
 
-    // Serialize a byte array as an array of bytes. Could also use a base64
-    // string here. Binary formats will typically represent byte arrays more
-    // compactly.
+    // Serialize a byte array as an array of bytes, or as a `Buffer` or
+    // `Uint8Array` depending on the configuration.
     fn serialize_bytes(self, v: &[u8]) -> Result<Self::Ok> {
+        match self.config.bytes {
+            BytesAs::Buffer => return create_buffer(self.env, v),
+            BytesAs::Uint8Array => return create_uint8array(self.env, v),
+            BytesAs::Array => {}
+        }
         use serde::ser::SerializeSeq;
         let mut seq = self.serialize_seq(Some(v.len()))?;
         for byte in v {
@@ -215,9 +491,13 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         seq.end()
     }
 
-    // An absent optional is represented as the JSON `null`.
+    // An absent optional is `null` by default, or `undefined`.
+    // Omitted properties are `undefined` here, skipped by the struct.
     fn serialize_none(self) -> Result<Self::Ok> {
-        self.serialize_unit()
+        match self.config.none {
+            NoneAs::Null => self.serialize_unit(),
+            _ => Ok(self.env.undefined()?.get_value())
+        }
     }
 
     // A present optional is represented as just the contained value. Note that
@@ -262,12 +542,17 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
     // insignificant wrappers around the data they contain.
     fn serialize_newtype_struct<T>(
         self,
-        _name: &'static str,
+        name: &'static str,
         value: &T,
     ) -> Result<Self::Ok>
     where
         T: ?Sized + Serialize,
     {
+        if name == HANDLE_MARKER {
+            if let Some(handle) = take_handle() {
+                return Ok(handle);
+            }
+        }
         value.serialize(self)
     }
 
@@ -287,7 +572,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         T: ?Sized + Serialize,
     {
         let obj = self.env.object()?;
-        obj.set(variant, serialize_to_js(self.env, value)?)?;
+        obj.set(variant, serialize_to_js_with(self.env, value, &self.config)?)?;
         Ok(obj.get_value())
     }
 
@@ -302,7 +587,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
     // explicitly in the serialized form. Some serializers may only be able to
     // support sequences for which the length is known up front.
     fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq> {
-        PinarArraySer::new(self.env, len.unwrap_or(0), None)
+        PinarArraySer::new(self.env, self.config, len.unwrap_or(0), None)
     }
 
     // Tuples look just like sequences in JSON. Some formats may be able to
@@ -310,7 +595,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
     // means that the corresponding `Deserialize implementation will know the
     // length without needing to look at the serialized data.
     fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple> {
-        PinarArraySer::new(self.env, len, None)
+        PinarArraySer::new(self.env, self.config, len, None)
     }
 
     // Tuple structs look just like sequences in JSON.
@@ -319,7 +604,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         _name: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleStruct> {
-        PinarArraySer::new(self.env, len, None)
+        PinarArraySer::new(self.env, self.config, len, None)
     }
 
     // Tuple variants are represented in JSON as `{ NAME: [DATA...] }`. Again
@@ -331,12 +616,16 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
-        PinarArraySer::new(self.env, len, Some(variant))
+        PinarArraySer::new(self.env, self.config, len, Some(variant))
     }
 
-    // Maps are represented in JSON as `{ K: V, K: V, ... }`.
+    // Maps are represented as `{ K: V, K: V, ... }`, or as a `Map`
+    // depending on the configuration.
     fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
-        PinarMapSer::new(self.env, None)
+        match self.config.map {
+            true => PinarMapSer::new_map(self.env, self.config),
+            _ => PinarMapSer::new(self.env, self.config, None)
+        }
     }
 
     // Structs look just like maps in JSON. In particular, JSON requires that we
@@ -349,7 +638,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         _name: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStruct> {
-        PinarMapSer::new(self.env, None)
+        PinarMapSer::new(self.env, self.config, None)
     }
 
     // Struct variants are represented in JSON as `{ NAME: { K: V, ... } }`.
@@ -361,7 +650,7 @@ impl<'e> ser::Serializer for PinarSerializer<'e> {
         variant: &'static str,
         _len: usize,
     ) -> Result<Self::SerializeStructVariant> {
-        PinarMapSer::new(self.env, Some(variant))
+        PinarMapSer::new(self.env, self.config, Some(variant))
     }
 }
 
@@ -384,7 +673,7 @@ impl<'e> ser::SerializeSeq for PinarArraySer<'e> {
         T: ?Sized + Serialize,
     {
         let index = self.current_index.get();
-        self.array.set(index as u32, serialize_to_js(self.env, value)?)?;
+        self.array.set(index as u32, serialize_to_js_with(self.env, value, &self.config)?)?;
         self.current_index.set(index + 1);
         Ok(())
     }
@@ -480,7 +769,7 @@ impl<'e> ser::SerializeMap for PinarMapSer<'e> {
     where
         T: ?Sized + Serialize,
     {
-        self.key = Some(serialize_to_js(self.env, key)?);
+        self.key = Some(self.to_js(key)?);
         Ok(())
     }
 
@@ -491,8 +780,11 @@ impl<'e> ser::SerializeMap for PinarMapSer<'e> {
     where
         T: ?Sized + Serialize,
     {
-        self.obj.set(self.key.unwrap(), serialize_to_js(self.env, value)?)?;
-        Ok(())
+        let key = match self.key.take() {
+            Some(key) => key,
+            _ => return Err(ser::Error::custom("map value serialized without a key"))
+        };
+        self.set(key, self.to_js(value)?)
     }
 
     fn serialize_entry<K: ?Sized, V: ?Sized>(&mut self, key: &K, value: &V) -> Result<()>
@@ -500,12 +792,11 @@ impl<'e> ser::SerializeMap for PinarMapSer<'e> {
         K: Serialize,
         V: Serialize,
     {
-        self.obj.set(serialize_to_js(self.env, key)?, serialize_to_js(self.env, value)?)?;
-        Ok(())
+        self.set(self.to_js(key)?, self.to_js(value)?)
     }
 
     fn end(self) -> Result<Self::Ok> {
-        Ok(self.obj.get_value())
+        Ok(self.into_value())
     }
 }
 
@@ -519,7 +810,7 @@ impl<'e> ser::SerializeStruct for PinarMapSer<'e> {
     where
         T: ?Sized + Serialize,
     {
-        ser::SerializeMap::serialize_entry(self, key, value)
+        self.set_field(key, value)
     }
 
     fn end(self) -> Result<Self::Ok> {
@@ -537,13 +828,59 @@ impl<'e> ser::SerializeStructVariant for PinarMapSer<'e> {
     where
         T: ?Sized + Serialize,
     {
-        ser::SerializeMap::serialize_entry(self, key, value)
+        self.set_field(key, value)
     }
 
     fn end(self) -> Result<Self::Ok> {
         let name = self.name_obj.unwrap();
         let obj = self.env.object()?;
-        obj.set(name, self.obj)?;
+        obj.set(name, self.into_value())?;
         Ok(obj.get_value())
     }
 }
+
+/// Creates a `Buffer` with a copy of the bytes
+fn create_buffer(env: Env, bytes: &[u8]) -> Result<Value> {
+    let mut result = Value::new(env);
+
+    napi_call!(napi_create_buffer_copy(
+        env.env(),
+        bytes.len(),
+        bytes.as_ptr() as *const std::ffi::c_void,
+        std::ptr::null_mut(),
+        result.get_mut()
+    ))?;
+
+    Ok(result)
+}
+
+/// Creates an `Uint8Array` with a copy of the bytes
+fn create_uint8array(env: Env, bytes: &[u8]) -> Result<Value> {
+    let mut data: *mut std::ffi::c_void = std::ptr::null_mut();
+    let mut buffer = Value::new(env);
+    let mut result = Value::new(env);
+
+    napi_call!(napi_create_arraybuffer(
+        env.env(),
+        bytes.len(),
+        &mut data,
+        buffer.get_mut()
+    ))?;
+
+    if !bytes.is_empty() {
+        unsafe {
+            std::ptr::copy_nonoverlapping(bytes.as_ptr(), data as *mut u8, bytes.len());
+        }
+    }
+
+    napi_call!(napi_create_typedarray(
+        env.env(),
+        napi_typedarray_type::napi_uint8_array,
+        bytes.len(),
+        buffer.get(),
+        0,
+        result.get_mut()
+    ))?;
+
+    Ok(result)
+}
@@ -0,0 +1,49 @@
+use napi_sys::*;
+use crate::prelude::*;
+
+/// The resolving side of a Javascript promise.
+///
+/// A `Deferred` can be sent to another thread, but it has to be
+/// resolved (or rejected) on the main JS thread.
+pub(crate) struct Deferred {
+    deferred: napi_deferred
+}
+
+// The deferred is only used from the main JS thread
+unsafe impl Send for Deferred {}
+
+impl Deferred {
+    /// Creates a new promise and its `Deferred`
+    pub(crate) fn new(env: Env) -> JsResult<(Deferred, Value)> {
+        let mut deferred: napi_deferred = std::ptr::null_mut();
+        let mut promise = Value::new(env);
+
+        napi_call!(napi_create_promise(
+            env.env(),
+            &mut deferred,
+            promise.get_mut()
+        ))?;
+
+        Ok((Deferred { deferred }, promise))
+    }
+
+    /// Fulfills the promise with `value`
+    pub(crate) fn resolve(self, env: Env, value: Value) -> JsResult<()> {
+        napi_call!(napi_resolve_deferred(
+            env.env(),
+            self.deferred,
+            value.get()
+        ))?;
+        Ok(())
+    }
+
+    /// Rejects the promise with `error`
+    pub(crate) fn reject(self, env: Env, error: Value) -> JsResult<()> {
+        napi_call!(napi_reject_deferred(
+            env.env(),
+            self.deferred,
+            error.get()
+        ))?;
+        Ok(())
+    }
+}
@@ -1,11 +1,16 @@
 
 use std::hash::Hash;
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, BTreeMap, HashSet, BTreeSet, VecDeque};
+use std::ffi::{OsStr, OsString};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::time::Duration;
 use std::sync::Arc;
 use std::rc::Rc;
 use std::hash::BuildHasher;
 use std::path::{Path, PathBuf};
 use crate::prelude::*;
+use crate::error::JsConversionError;
 
 /// Trait to convert a Rust value to Javascript
 ///
@@ -53,6 +58,8 @@ impl_tojs!(
     JsNull,
     JsBoolean,
     JsBigInt,
+    JsMap,
+    JsSet,
     JsAny
 );
 
@@ -63,10 +70,135 @@ impl<'e> ToJs<'e> for Value {
     }
 }
 
-impl<'e> ToJs<'e> for i64 {
+/// Integers above this value can't be represented exactly in javascript
+/// (`Number.MAX_SAFE_INTEGER`)
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+macro_rules! impl_tojs_int {
+    (
+        EXACT: $( $exact:ident ),*;
+        CHECKED: $( $checked:ident ),*
+    ) => {
+        $(
+            impl<'e> ToJs<'e> for $exact {
+                type Value = JsNumber<'e>;
+                fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
+                    env.number(i64::from(*self))
+                }
+            }
+        )*
+        $(
+            impl<'e> ToJs<'e> for $checked {
+                type Value = JsNumber<'e>;
+                #[allow(clippy::cast_lossless, clippy::unnecessary_cast)]
+                fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
+                    let number = *self as i128;
+                    if number > MAX_SAFE_INTEGER as i128 || number < -MAX_SAFE_INTEGER as i128 {
+                        return Err(JsConversionError::OutOfRange(self.to_string(), stringify!($checked)).into());
+                    }
+                    env.number(number as i64)
+                }
+            }
+        )*
+    }
+}
+
+impl_tojs_int!(
+    EXACT: i8, i16, i32, u8, u16, u32;
+    CHECKED: i64, u64, i128, isize, usize
+);
+
+impl<'e> ToJs<'e> for u128 {
+    type Value = JsNumber<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
+        if *self > MAX_SAFE_INTEGER as u128 {
+            return Err(JsConversionError::OutOfRange(self.to_string(), "u128").into());
+        }
+        env.number(*self as i64)
+    }
+}
+
+impl<'e> ToJs<'e> for f64 {
+    type Value = JsNumber<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
+        env.double(*self)
+    }
+}
+
+impl<'e> ToJs<'e> for f32 {
     type Value = JsNumber<'e>;
     fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
-        env.number(*self)
+        env.double(f64::from(*self))
+    }
+}
+
+impl<'e> ToJs<'e> for char {
+    type Value = JsString<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
+        env.string(self.encode_utf8(&mut [0; 4]))
+    }
+}
+
+/// `None` is converted to `undefined`
+impl<'e, T> ToJs<'e> for Option<T>
+where
+    T: ToJs<'e>
+{
+    type Value = Value;
+    fn to_js(&self, env: Env) -> JsResult<Value> {
+        match self {
+            Some(value) => Ok(value.to_js(env)?.get_value()),
+            _ => Ok(env.undefined()?.get_value())
+        }
+    }
+}
+
+/// The duration is converted to milliseconds
+impl<'e> ToJs<'e> for Duration {
+    type Value = JsNumber<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsNumber<'e>> {
+        env.double(self.as_secs_f64() * 1000.0)
+    }
+}
+
+macro_rules! impl_tojs_display {
+    (
+        $( $rtype:ident ),*
+    ) => {
+        $(
+            impl<'e> ToJs<'e> for $rtype {
+                type Value = JsString<'e>;
+                fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
+                    env.string(self.to_string())
+                }
+            }
+        )*
+    }
+}
+
+impl_tojs_display!(IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr);
+
+impl<'e> ToJs<'e> for OsStr {
+    type Value = JsString<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
+        match self.to_str() {
+            Some(s) => env.string(s),
+            _ => Err(JsConversionError::NotUtf8(self.to_string_lossy().into_owned()).into())
+        }
+    }
+}
+
+impl<'e> ToJs<'e> for OsString {
+    type Value = JsString<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
+        self.as_os_str().to_js(env)
+    }
+}
+
+impl<'e> ToJs<'e> for Cow<'_, str> {
+    type Value = JsString<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
+        env.string(self)
     }
 }
 
@@ -97,7 +229,7 @@ impl<'e> ToJs<'e> for serde_json::Value {
     type Value = Value;
 
     fn to_js(&self, env: Env) -> JsResult<Value> {
-        Ok(serialize_to_js(env, self).unwrap())
+        Ok(serialize_to_js(env, self)?)
     }
 }
 
@@ -124,31 +256,84 @@ where
     }
 }
 
-// impl<'e, A, R> ToJs<'e> for fn(A) -> R
-// where
-//     A: FromArguments + 'static,
-//     R: for<'env> JsReturn<'env> + 'static,
-// {
-//     type Value = JsFunction<'e>;
-//     fn to_js(&self, env: Env) -> JsResult<JsFunction<'e>> {
-//         env.function("_pinar_anonymous_", self)
-//     }
-// }
+impl<'e, K, V> ToJs<'e> for BTreeMap<K, V>
+where
+    K: Ord + KeyProperty + ToJs<'e>,
+    V: ToJs<'e>
+{
+    type Value = JsObject<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsObject<'e>> {
+        let object = env.object()?;
+        for (key, value) in self.iter() {
+            object.set_ref(key, value)?;
+        }
+        Ok(object)
+    }
+}
 
-// This doesn't work :(
-// Will probably work with Chalk and specialization ?
-//
-// impl<'e, F, A, R> ToJs<'e, A, R> for F
-// where
-//     A: FromArguments + 'static,
-//     R: for<'env> JsReturn<'env> + 'static,
-//     F: Fn(A) -> R + 'static
-// {
-//     type Value = JsFunction<'e>;
-//     fn to_js(&self, env: Env) -> JsResult<JsFunction<'e>> {
-//         //env.function("_pinar_anonymous_", self)
-//     }
-// }
+impl<'e, K, V, S> ToJs<'e> for AsMap<HashMap<K, V, S>>
+where
+    K: Hash + Eq + ToJs<'e>,
+    V: ToJs<'e>,
+    S: BuildHasher
+{
+    type Value = JsMap<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsMap<'e>> {
+        let map = JsMap::new(env)?;
+        for (key, value) in self.iter() {
+            map.set(key.to_js(env)?.get_value(), value.to_js(env)?.get_value())?;
+        }
+        Ok(map)
+    }
+}
+
+impl<'e, K, V> ToJs<'e> for AsMap<BTreeMap<K, V>>
+where
+    K: Ord + ToJs<'e>,
+    V: ToJs<'e>
+{
+    type Value = JsMap<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsMap<'e>> {
+        let map = JsMap::new(env)?;
+        for (key, value) in self.iter() {
+            map.set(key.to_js(env)?.get_value(), value.to_js(env)?.get_value())?;
+        }
+        Ok(map)
+    }
+}
+
+impl<'e, T, S> ToJs<'e> for AsSet<HashSet<T, S>>
+where
+    T: Hash + Eq + ToJs<'e>,
+    S: BuildHasher
+{
+    type Value = JsSet<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsSet<'e>> {
+        let set = JsSet::new(env)?;
+        for value in self.iter() {
+            set.add(value.to_js(env)?.get_value())?;
+        }
+        Ok(set)
+    }
+}
+
+impl<'e, T> ToJs<'e> for AsSet<BTreeSet<T>>
+where
+    T: Ord + ToJs<'e>
+{
+    type Value = JsSet<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsSet<'e>> {
+        let set = JsSet::new(env)?;
+        for value in self.iter() {
+            set.add(value.to_js(env)?.get_value())?;
+        }
+        Ok(set)
+    }
+}
+
+// Closures can't implement ToJs directly: the arguments and return types
+// are not constrained by the trait.
+// They are converted with the wrapper `JsClosure`.
 
 impl<'e, T> ToJs<'e> for std::vec::Vec<T>
 where
@@ -164,6 +349,74 @@ where
     }
 }
 
+impl<'e, T> ToJs<'e> for VecDeque<T>
+where
+    T: ToJs<'e>
+{
+    type Value = JsArray<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsArray<'e>> {
+        let array = env.array_with_capacity(self.len())?;
+        for (index, value) in self.iter().enumerate() {
+            array.set_ref(index as u32, value)?;
+        }
+        Ok(array)
+    }
+}
+
+impl<'e, T> ToJs<'e> for Box<[T]>
+where
+    T: ToJs<'e>
+{
+    type Value = JsArray<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsArray<'e>> {
+        let array = env.array_with_capacity(self.len())?;
+        for (index, value) in self.iter().enumerate() {
+            array.set_ref(index as u32, value)?;
+        }
+        Ok(array)
+    }
+}
+
+/// `()` is converted to `undefined`
+impl<'e> ToJs<'e> for () {
+    type Value = JsUndefined<'e>;
+    fn to_js(&self, env: Env) -> JsResult<JsUndefined<'e>> {
+        env.undefined()
+    }
+}
+
+macro_rules! impl_tojs_tuples {
+    (
+        $( ($len:tt ; $($tuple:ident, $n:tt),* ) ),*
+    ) => {
+        $(
+            /// The tuple is converted to an array
+            impl<'e, $($tuple),*> ToJs<'e> for ($($tuple,)*)
+            where
+                $($tuple : ToJs<'e>,)*
+            {
+                type Value = JsArray<'e>;
+                fn to_js(&self, env: Env) -> JsResult<JsArray<'e>> {
+                    let array = env.array_with_capacity($len)?;
+                    $(array.set_ref($n, &self.$n)?;)*
+                    Ok(array)
+                }
+            }
+        )*
+    }
+}
+
+impl_tojs_tuples!(
+    (1;A,0),
+    (2;A,0,B,1),
+    (3;A,0,B,1,C,2),
+    (4;A,0,B,1,C,2,D,3),
+    (5;A,0,B,1,C,2,D,3,E,4),
+    (6;A,0,B,1,C,2,D,3,E,4,F,5),
+    (7;A,0,B,1,C,2,D,3,E,4,F,5,G,6),
+    (8;A,0,B,1,C,2,D,3,E,4,F,5,G,6,H,7)
+);
+
 impl<'e> ToJs<'e> for str {
     type Value = JsString<'e>;
     fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
@@ -171,10 +424,13 @@ impl<'e> ToJs<'e> for str {
     }
 }
 
-impl<'e> ToJs<'e> for &'_ str {
-    type Value = JsString<'e>;
-    fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
-        env.string(self)
+impl<'e, T> ToJs<'e> for &'_ T
+where
+    T: ToJs<'e> + ?Sized
+{
+    type Value = T::Value;
+    fn to_js(&self, env: Env) -> JsResult<T::Value> {
+        (**self).to_js(env)
     }
 }
 
@@ -204,7 +460,7 @@ impl<'e> ToJs<'e> for &'_ str {
 //     }
 // }
 
-impl<'e, 'p> ToJs<'e> for &'p Path {
+impl<'e> ToJs<'e> for Path {
     type Value = JsString<'e>;
     fn to_js(&self, env: Env) -> JsResult<JsString<'e>> {
         env.string(self.as_os_str().to_str().unwrap())
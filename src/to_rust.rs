@@ -1,10 +1,17 @@
 
 use std::rc::Rc;
 use std::sync::Arc;
-use std::os::raw::c_char;
 use napi_sys::*;
 use std::path::PathBuf;
+use std::borrow::Cow;
+use std::collections::{HashMap, BTreeMap, HashSet, BTreeSet, VecDeque};
+use std::ffi::OsString;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use std::time::Duration;
+use std::hash::{Hash, BuildHasher};
 use crate::prelude::*;
+use crate::error::JsConversionError;
 
 /// Trait to convert a Javascript value to Rust
 ///
@@ -24,26 +31,13 @@ pub trait ToRust<R> {
     fn to_rust(&self) -> JsResult<R>;
 }
 
+/// The string is read in the scratch buffer of the thread, with a single
+/// napi call once the buffer is large enough, then copied in a `String` of
+/// the exact size.
 impl<'e> ToRust<String> for JsString<'e>
 {
     fn to_rust(&self) -> JsResult<String> {
-        let len = self.len()?;
-        let mut buffer: Vec<u8> = Vec::with_capacity(len + 1); // + '\0'
-        let mut written = 0usize;
-
-        napi_call!(napi_get_value_string_utf8(
-            self.value.env(),
-            self.value.get(),
-            buffer.as_mut_ptr() as *mut c_char,
-            len + 1,
-            &mut written as *mut usize
-        ))?;
-
-        unsafe {
-            buffer.set_len(written);
-            // It's probably safe to assume that it's valid ut8
-            Ok(String::from_utf8_unchecked(buffer))
-        }
+        self.read(|bytes| bytes.into_string())
     }
 }
 
@@ -55,62 +49,125 @@ impl<'e> ToRust<PathBuf> for JsString<'e>
     }
 }
 
-impl<'e> ToRust<i64> for JsNumber<'e> {
-    fn to_rust(&self) -> JsResult<i64> {
-        let mut number = 0i64;
+/// Checks that `number` is an integer in the range of an integer type of
+/// `bits` bits, named `ty` in the error
+#[allow(clippy::float_cmp)]
+pub(crate) fn check_integer(number: f64, ty: &'static str, bits: u32, signed: bool) -> Result<f64, JsConversionError> {
+    // The bounds are powers of 2, exact in a f64. The end is exclusive:
+    // `MAX as f64` is rounded up for the types of 64 bits and more
+    let (start, end) = match signed {
+        true => (-(2f64.powi(bits as i32 - 1)), 2f64.powi(bits as i32 - 1)),
+        _ => (0.0, 2f64.powi(bits as i32))
+    };
+
+    if number.trunc() != number {
+        return Err(JsConversionError::NotInteger(number, ty));
+    }
+    if !(start..end).contains(&number) {
+        return Err(JsConversionError::OutOfRange(number.to_string(), ty));
+    }
 
-        napi_call!(napi_get_value_int64(
-            self.value.env(),
-            self.value.get(),
-            &mut number as *mut i64
-        ))?;
+    Ok(number)
+}
 
-        Ok(number)
+macro_rules! impl_torust_int {
+    (
+        $( $rtype:ident ),*
+    ) => {
+        $(
+            /// The number has to be an integer in the range of the type
+            impl<'e> ToRust<$rtype> for JsNumber<'e> {
+                fn to_rust(&self) -> JsResult<$rtype> {
+                    let number: f64 = self.to_rust()?;
+                    let number = check_integer(number, stringify!($rtype), $rtype::BITS, $rtype::MIN != 0)?;
+                    Ok(number as $rtype)
+                }
+            }
+        )*
     }
 }
 
-impl<'e> ToRust<i32> for JsNumber<'e> {
-    fn to_rust(&self) -> JsResult<i32> {
-        let mut number = 0i32;
+impl_torust_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
 
-        napi_call!(napi_get_value_int32(
+impl<'e> ToRust<f64> for JsNumber<'e> {
+    fn to_rust(&self) -> JsResult<f64> {
+        let mut number = 0f64;
+
+        napi_call!(napi_get_value_double(
             self.value.env(),
             self.value.get(),
-            &mut number as *mut i32
+            &mut number as *mut f64
         ))?;
 
         Ok(number)
     }
 }
 
-impl<'e> ToRust<u32> for JsNumber<'e> {
-    fn to_rust(&self) -> JsResult<u32> {
-        let mut number = 0u32;
+impl<'e> ToRust<f32> for JsNumber<'e> {
+    fn to_rust(&self) -> JsResult<f32> {
+        let number: f64 = self.to_rust()?;
+        Ok(number as f32)
+    }
+}
 
-        napi_call!(napi_get_value_uint32(
-            self.value.env(),
-            self.value.get(),
-            &mut number as *mut u32
-        ))?;
+/// The number is a duration in milliseconds
+impl<'e> ToRust<Duration> for JsNumber<'e> {
+    fn to_rust(&self) -> JsResult<Duration> {
+        let millis: f64 = self.to_rust()?;
+        Duration::try_from_secs_f64(millis / 1000.0).map_err(|_| {
+            JsConversionError::OutOfRange(millis.to_string(), "Duration").into()
+        })
+    }
+}
 
-        Ok(number)
+impl<'e> ToRust<char> for JsString<'e> {
+    fn to_rust(&self) -> JsResult<char> {
+        let string: String = self.to_rust()?;
+        let mut chars = string.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(JsConversionError::NotChar(string).into())
+        }
     }
 }
 
-impl<'e> ToRust<f64> for JsNumber<'e> {
-    fn to_rust(&self) -> JsResult<f64> {
-        let mut number = 0f64;
+impl<'e> ToRust<Cow<'static, str>> for JsString<'e> {
+    fn to_rust(&self) -> JsResult<Cow<'static, str>> {
+        let string: String = self.to_rust()?;
+        Ok(Cow::Owned(string))
+    }
+}
 
-        napi_call!(napi_get_value_double(
-            self.value.env(),
-            self.value.get(),
-            &mut number as *mut f64
-        ))?;
+impl<'e> ToRust<OsString> for JsString<'e> {
+    fn to_rust(&self) -> JsResult<OsString> {
+        let string: String = self.to_rust()?;
+        Ok(OsString::from(string))
+    }
+}
 
-        Ok(number)
+macro_rules! impl_torust_parse {
+    (
+        $( ( $rtype:ident, $name:expr ) ),*
+    ) => {
+        $(
+            impl<'e> ToRust<$rtype> for JsString<'e> {
+                fn to_rust(&self) -> JsResult<$rtype> {
+                    let string: String = self.to_rust()?;
+                    string.parse()
+                          .map_err(|_| JsConversionError::Parse($name, string).into())
+                }
+            }
+        )*
     }
 }
 
+impl_torust_parse!(
+    (IpAddr, "IP address"),
+    (Ipv4Addr, "IPv4 address"),
+    (Ipv6Addr, "IPv6 address"),
+    (SocketAddr, "socket address")
+);
+
 impl<'e> ToRust<bool> for JsBoolean<'e> {
     fn to_rust(&self) -> JsResult<bool> {
         let mut result = false;
@@ -126,14 +183,105 @@ impl<'e> ToRust<bool> for JsBoolean<'e> {
 }
 
 
-#[cfg(feature = "json")]
-impl<'e> ToRust<serde_json::Value> for JsAny<'e> {
-    fn to_rust(&self) -> JsResult<serde_json::Value> {
-        crate::pinar_serde::de::from_any(self.env(), self.clone())
-            .map_err(|e| e.into())
+/// Converts the value to any type implementing `Deserialize`.
+///
+/// See [`JsAny::deserialize`].
+///
+/// [`JsAny::deserialize`]: ./enum.JsAny.html#method.deserialize
+#[cfg(feature = "pinar-serde")]
+impl<'e, T> ToRust<T> for JsAny<'e>
+where
+    T: serde::de::DeserializeOwned
+{
+    fn to_rust(&self) -> JsResult<T> {
+        self.deserialize()
+    }
+}
+
+macro_rules! torust_any {
+    (
+        JS_TYPES:
+        $( ( $jstype:ident, $variant:ident, $str:expr ) ),*,
+        RUST_TYPES:
+        $( ( $rtype:ty, $rvariant:ident, $rstr:expr ) ),*
+    ) => {
+        $(
+            impl<'e> ToRust<$jstype<'e>> for JsAny<'e> {
+                fn to_rust(&self) -> JsResult<$jstype<'e>> {
+                    match self {
+                        JsAny::$variant(value) => Ok(value.clone()),
+                        other => Err(JsConversionError::WrongType($str, other.type_name()).into())
+                    }
+                }
+            }
+        )*
+        $(
+            impl<'e> ToRust<$rtype> for JsAny<'e> {
+                fn to_rust(&self) -> JsResult<$rtype> {
+                    match self {
+                        JsAny::$rvariant(value) => value.to_rust(),
+                        other => Err(JsConversionError::WrongType($rstr, other.type_name()).into())
+                    }
+                }
+            }
+        )*
+    }
+}
+
+// The other conversions are covered by `Deserialize`
+torust_any!(
+    JS_TYPES:
+    (JsString, String, "string"),
+    (JsObject, Object, "object"),
+    (JsArray, Array, "array"),
+    (JsNumber, Number, "number"),
+    (JsSymbol, Symbol, "symbol"),
+    (JsUndefined, Undefined, "undefined"),
+    (JsNull, Null, "null"),
+    (JsBoolean, Boolean, "boolean"),
+    (JsBigInt, BigInt, "bigint"),
+    RUST_TYPES:
+);
+
+#[cfg(not(feature = "pinar-serde"))]
+impl<'e> ToRust<JsAny<'e>> for JsAny<'e> {
+    fn to_rust(&self) -> JsResult<JsAny<'e>> {
+        Ok(self.clone())
     }
 }
 
+#[cfg(not(feature = "pinar-serde"))]
+torust_any!(
+    JS_TYPES:
+    (JsFunction, Function, "function"),
+    (JsExternal, External, "external"),
+    RUST_TYPES:
+    (i8, Number, "number"),
+    (i16, Number, "number"),
+    (i32, Number, "number"),
+    (i64, Number, "number"),
+    (i128, Number, "number"),
+    (isize, Number, "number"),
+    (u8, Number, "number"),
+    (u16, Number, "number"),
+    (u32, Number, "number"),
+    (u64, Number, "number"),
+    (u128, Number, "number"),
+    (usize, Number, "number"),
+    (f32, Number, "number"),
+    (f64, Number, "number"),
+    (Duration, Number, "number"),
+    (String, String, "string"),
+    (char, String, "string"),
+    (PathBuf, String, "string"),
+    (OsString, String, "string"),
+    (IpAddr, String, "string"),
+    (Ipv4Addr, String, "string"),
+    (Ipv6Addr, String, "string"),
+    (SocketAddr, String, "string"),
+    (bool, Boolean, "boolean")
+);
+
 // impl<'e, T> ToRust<T> for JsAny<'e>
 // where
 //     JsString<'e>: ToRust<T>,
@@ -222,19 +370,170 @@ impl<'e, T: 'static> ToRust<Option<Box<T>>> for JsExternal<'e> {
     }
 }
 
-use serde::de::DeserializeOwned;
-
 impl<'e, T> ToRust<Vec<T>> for JsArray<'e>
 where
-    T: DeserializeOwned
+    JsAny<'e>: ToRust<T>
 {
     fn to_rust(&self) -> JsResult<Vec<T>> {
-        let env = self.value.env;
-        let mut vec = Vec::with_capacity(self.len()?);
-        for elem in self.iter()? {
-            let elem: JsResult<_> = pinar_serde::de::from_any::<T>(env, elem).map_err(Into::into);
-            vec.push(elem?);
+        let len = self.len()?;
+        let mut vec = Vec::with_capacity(len);
+        for index in 0..len {
+            vec.push(self.get(index as u32)?.to_rust()?);
         }
         Ok(vec)
     }
 }
+
+impl<'e, T> ToRust<VecDeque<T>> for JsArray<'e>
+where
+    JsAny<'e>: ToRust<T>
+{
+    fn to_rust(&self) -> JsResult<VecDeque<T>> {
+        let vec: Vec<T> = self.to_rust()?;
+        Ok(vec.into())
+    }
+}
+
+impl<'e, T> ToRust<Box<[T]>> for JsArray<'e>
+where
+    JsAny<'e>: ToRust<T>
+{
+    fn to_rust(&self) -> JsResult<Box<[T]>> {
+        let vec: Vec<T> = self.to_rust()?;
+        Ok(vec.into_boxed_slice())
+    }
+}
+
+macro_rules! torust_tuples {
+    (
+        $( ( $len:expr, $($tuple:ident),* ) ),*
+    ) => {
+        $(
+            /// The array must have the same length than the tuple
+            impl<'e, $($tuple),*> ToRust<($($tuple,)*)> for JsArray<'e>
+            where
+                $(JsAny<'e> : ToRust<$tuple>,)*
+            {
+                #[allow(unused_assignments)]
+                fn to_rust(&self) -> JsResult<($($tuple,)*)> {
+                    let len = self.len()?;
+                    if len != $len {
+                        return Err(JsConversionError::Length($len, len).into());
+                    }
+                    let mut index = 0;
+                    Ok(($({
+                        let value: $tuple = self.get(index)?.to_rust()?;
+                        index += 1;
+                        value
+                    },)*))
+                }
+            }
+        )*
+    }
+}
+
+torust_tuples!(
+    (1, A),
+    (2, A, B),
+    (3, A, B, C),
+    (4, A, B, C, D),
+    (5, A, B, C, D, E),
+    (6, A, B, C, D, E, F),
+    (7, A, B, C, D, E, F, G),
+    (8, A, B, C, D, E, F, G, H)
+);
+
+/// The keys of the object are parsed with `FromStr`
+impl<'e, K, V> ToRust<BTreeMap<K, V>> for JsObject<'e>
+where
+    K: FromStr + Ord,
+    JsAny<'e>: ToRust<V>
+{
+    fn to_rust(&self) -> JsResult<BTreeMap<K, V>> {
+        object_entries(self)
+    }
+}
+
+/// The keys of the object are parsed with `FromStr`
+impl<'e, K, V, S> ToRust<HashMap<K, V, S>> for JsObject<'e>
+where
+    K: FromStr + Hash + Eq,
+    JsAny<'e>: ToRust<V>,
+    S: BuildHasher + Default
+{
+    fn to_rust(&self) -> JsResult<HashMap<K, V, S>> {
+        object_entries(self)
+    }
+}
+
+/// Collects the enumerable properties of the object, converted to Rust
+fn object_entries<'e, K, V, C>(object: &JsObject<'e>) -> JsResult<C>
+where
+    K: FromStr,
+    JsAny<'e>: ToRust<V>,
+    C: std::iter::FromIterator<(K, V)>
+{
+    let keys = object.get_property_names()?;
+
+    keys.into_iter().map(|key| {
+        let value = object.get(key.as_str())?.to_rust()?;
+        match key.parse() {
+            Ok(key) => Ok((key, value)),
+            _ => Err(JsConversionError::Parse("key", key).into())
+        }
+    }).collect()
+}
+
+impl<'e, K, V, S> ToRust<HashMap<K, V, S>> for JsMap<'e>
+where
+    K: Hash + Eq,
+    JsAny<'e>: ToRust<K> + ToRust<V>,
+    S: BuildHasher + Default
+{
+    fn to_rust(&self) -> JsResult<HashMap<K, V, S>> {
+        self.entries()?
+            .into_iter()
+            .map(|(key, value)| Ok((key.to_rust()?, value.to_rust()?)))
+            .collect()
+    }
+}
+
+impl<'e, K, V> ToRust<BTreeMap<K, V>> for JsMap<'e>
+where
+    K: Ord,
+    JsAny<'e>: ToRust<K> + ToRust<V>
+{
+    fn to_rust(&self) -> JsResult<BTreeMap<K, V>> {
+        self.entries()?
+            .into_iter()
+            .map(|(key, value)| Ok((key.to_rust()?, value.to_rust()?)))
+            .collect()
+    }
+}
+
+impl<'e, T, S> ToRust<HashSet<T, S>> for JsSet<'e>
+where
+    T: Hash + Eq,
+    JsAny<'e>: ToRust<T>,
+    S: BuildHasher + Default
+{
+    fn to_rust(&self) -> JsResult<HashSet<T, S>> {
+        self.values()?
+            .into_iter()
+            .map(|value| value.to_rust())
+            .collect()
+    }
+}
+
+impl<'e, T> ToRust<BTreeSet<T>> for JsSet<'e>
+where
+    T: Ord,
+    JsAny<'e>: ToRust<T>
+{
+    fn to_rust(&self) -> JsResult<BTreeSet<T>> {
+        self.values()?
+            .into_iter()
+            .map(|value| value.to_rust())
+            .collect()
+    }
+}
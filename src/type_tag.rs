@@ -0,0 +1,105 @@
+use std::any::TypeId;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use napi_sys::{napi_env, napi_value, napi_status};
+
+use crate::prelude::*;
+use crate::status::Status;
+
+/// A 128-bit tag attached to a Javascript object or external value.
+///
+/// The tag identifies the native data of the object: unlike `napi_unwrap`,
+/// it doesn't rely on the object being created by this addon.  
+/// Pinar tags the class instances and the external values with the tag of
+/// their Rust type.
+///
+/// An object can be tagged only once.
+///
+/// Type tags require N-API 8 (Node 12.22, 14.17, 15.12, 16.0 or later) and
+/// are enabled with the `napi8` feature of pinar.  
+/// Without it, pinar identifies its class instances and external values
+/// with the Rust type id stored at the start of their native data.
+///
+/// # Example
+///
+/// ```
+/// // A tag shared with another native addon
+/// const DATABASE_TAG: TypeTag = TypeTag::new(0x9c73_317f_9fad_44a7, 0x8b40_c856_3e72_0e2e);
+///
+/// #[pinar]
+/// fn query(db: JsObject) -> JsResult<()> {
+///     if !db.has_type_tag(DATABASE_TAG)? {
+///         return Err(...);
+///     }
+///     Ok(())
+/// }
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TypeTag {
+    pub lower: u64,
+    pub upper: u64,
+}
+
+impl TypeTag {
+    /// Creates a tag from its 2 halves
+    pub const fn new(lower: u64, upper: u64) -> TypeTag {
+        TypeTag { lower, upper }
+    }
+
+    /// Returns the tag of the Rust type `T`.
+    ///
+    /// The tag is the same for the whole build of the addon.
+    pub fn of<T: 'static>() -> TypeTag {
+        let mut hasher = DefaultHasher::new();
+        TypeId::of::<T>().hash(&mut hasher);
+        let lower = hasher.finish();
+
+        std::any::type_name::<T>().hash(&mut hasher);
+        let upper = hasher.finish();
+
+        TypeTag { lower, upper }
+    }
+}
+
+// Available from N-API 8, not declared by napi-sys 0.1
+extern "C" {
+    fn napi_type_tag_object(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const TypeTag
+    ) -> napi_status;
+
+    fn napi_check_object_type_tag(
+        env: napi_env,
+        value: napi_value,
+        type_tag: *const TypeTag,
+        result: *mut bool
+    ) -> napi_status;
+}
+
+/// Tags the object or external `value`
+pub(crate) fn tag_value(value: Value, tag: TypeTag) -> JsResult<()> {
+    napi_call!(napi_type_tag_object(
+        value.env(),
+        value.get(),
+        &tag
+    ))?;
+
+    Ok(())
+}
+
+/// Returns true if the object or external `value` has the tag
+pub(crate) fn has_tag(value: Value, tag: TypeTag) -> JsResult<bool> {
+    let mut result = false;
+
+    napi_call!(napi_check_object_type_tag(
+        value.env(),
+        value.get(),
+        &tag,
+        &mut result
+    ))?;
+
+    Ok(result)
+}
@@ -32,10 +32,7 @@
   //  console.log("class:", myclass.easy())
   //myclass.easy3 = undefined;
   console.log("easy3:", myclass.easy3)
-  console.log("data:", myclass.__pinar_class_data);
-  myclass.__pinar_class_data = undefined;
-  delete myclass.__pinar_class_data;
-  console.log("data:", myclass.__pinar_class_data);
+  console.log("keys:", Object.keys(myclass));
   //console.log("EASY:", myclass.easy(234));
   console.log("EASY:", myclass.easy("seb", 234));
   // console.log("EASY2:", myclass.easy2("sdfs", 234));